@@ -15,6 +15,7 @@ fn main() {
     let a = vec![1.0, -2.0, 3.0, 5.0, 1.0, 2.0, 4.0, -7.0, 5.0];
     let ia = vec![1, 4, 7, 9, 10];
     let ja = vec![1, 3, 4, 2, 3, 4, 3, 4, 4];
+    let matrix = CsrMatrix::new(n as usize, &a, &ia, &ja).unwrap();
 
     // Generate some right hand side data
     let mut b: Vec<f64> = (0..(n * m)).map(|x| x as f64).collect();
@@ -34,18 +35,18 @@ fn main() {
 
     // compute the symbolic factorization
     ps.set_phase(Phase::Analysis);
-    ps.pardiso(&a, &ia, &ja, &mut [], &mut [], n, 1).unwrap();
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
 
     // compute the numeric factorization
     ps.set_phase(Phase::NumFact);
-    ps.pardiso(&a, &ia, &ja, &mut b, &mut x, n, m).unwrap();
+    ps.pardiso(&matrix, &mut b, &mut x, m).unwrap();
 
     // compute the solutions
     ps.set_phase(Phase::SolveIterativeRefine);
-    ps.pardiso(&a, &ia, &ja, &mut b, &mut x, n, m).unwrap();
+    ps.pardiso(&matrix, &mut b, &mut x, m).unwrap();
 
     // Free solver resources
     // Unnecessary (but harmless) since Drop impl does this for you
     ps.set_phase(Phase::ReleaseAll);
-    ps.pardiso(&a, &ia, &ja, &mut b, &mut x, n, m).unwrap();
+    ps.pardiso(&matrix, &mut b, &mut x, m).unwrap();
 }
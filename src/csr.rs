@@ -0,0 +1,206 @@
+use crate::{IndexBase, PardisoError, PardisoScalar};
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for i32 {}
+    impl Sealed for i64 {}
+}
+
+/// Integer types accepted for CSR row pointers and column indices.
+///
+/// `i32` indices use the standard Pardiso interface, while `i64` indices
+/// select the 64-bit integer (ILP64) interface where the backend has one.
+pub trait PardisoIndex:
+    private::Sealed + Copy + Into<i64> + TryFrom<i64> + std::fmt::Debug + 'static
+{
+    const IS_64: bool;
+}
+
+impl PardisoIndex for i32 {
+    const IS_64: bool = false;
+}
+
+impl PardisoIndex for i64 {
+    const IS_64: bool = true;
+}
+
+/// A borrowed square sparse matrix in compressed sparse row (CSR) format.
+///
+/// The row pointers `ia`, column indices `ja` and values `a` are checked
+/// for structural consistency on construction, so that a `CsrMatrix` can
+/// be passed to the solver without risk of out-of-bounds access inside
+/// the Pardiso library.  The index base (0 or 1) is taken from `ia[0]`
+/// and passed on to the backend, so either convention can be used
+/// without further configuration.
+#[derive(Debug, Clone, Copy)]
+pub struct CsrMatrix<'a, T: PardisoScalar = f64, I: PardisoIndex = i32> {
+    n: usize,
+    base: IndexBase,
+    a: &'a [T],
+    ia: &'a [I],
+    ja: &'a [I],
+}
+
+impl<'a, T: PardisoScalar, I: PardisoIndex> CsrMatrix<'a, T, I> {
+    pub fn new(n: usize, a: &'a [T], ia: &'a [I], ja: &'a [I]) -> Result<Self, PardisoError> {
+        if i64::try_from(n)
+            .ok()
+            .and_then(|n| I::try_from(n).ok())
+            .is_none()
+        {
+            return Err(PardisoError::DimensionOverflow(n));
+        }
+        if ia.len() != n + 1 {
+            return Err(PardisoError::RowPointerLength {
+                expected: n + 1,
+                found: ia.len(),
+            });
+        }
+
+        let first: i64 = ia[0].into();
+        let base = match first {
+            0 => IndexBase::Zero,
+            1 => IndexBase::One,
+            b => return Err(PardisoError::InvalidIndexBase(b)),
+        };
+
+        if let Some(row) = ia.windows(2).position(|w| w[1].into() < w[0].into()) {
+            return Err(PardisoError::RowPointerNotMonotone { row });
+        }
+
+        let offset = |i: I| (i.into() - first) as usize;
+        let nnz = offset(ia[n]);
+        if ja.len() != nnz {
+            return Err(PardisoError::ColumnIndexLength {
+                expected: nnz,
+                found: ja.len(),
+            });
+        }
+        if a.len() != nnz {
+            return Err(PardisoError::ValuesLength {
+                expected: nnz,
+                found: a.len(),
+            });
+        }
+
+        // column indices must lie in [base, n - 1 + base]
+        let lo = base as i64;
+        let hi = n as i64 - 1 + lo;
+        for row in 0..n {
+            let start = offset(ia[row]);
+            let stop = offset(ia[row + 1]);
+            for &j in &ja[start..stop] {
+                let j: i64 = j.into();
+                if j < lo || j > hi {
+                    return Err(PardisoError::ColumnIndexOutOfRange { row, index: j });
+                }
+            }
+        }
+
+        Ok(Self { n, base, a, ia, ja })
+    }
+
+    // Same sparsity pattern, new values.  Used to refactor a matrix
+    // without revalidating its structure
+    pub fn with_values<'b, U: PardisoScalar>(
+        &self,
+        a: &'b [U],
+    ) -> Result<CsrMatrix<'b, U, I>, PardisoError>
+    where
+        'a: 'b,
+    {
+        if a.len() != self.nnz() {
+            return Err(PardisoError::ValuesLength {
+                expected: self.nnz(),
+                found: a.len(),
+            });
+        }
+        Ok(CsrMatrix {
+            n: self.n,
+            base: self.base,
+            a,
+            ia: self.ia,
+            ja: self.ja,
+        })
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+    pub fn nnz(&self) -> usize {
+        self.ja.len()
+    }
+    pub fn base(&self) -> IndexBase {
+        self.base
+    }
+    pub fn values(&self) -> &'a [T] {
+        self.a
+    }
+    pub fn row_ptrs(&self) -> &'a [I] {
+        self.ia
+    }
+    pub fn col_indices(&self) -> &'a [I] {
+        self.ja
+    }
+}
+
+impl<'a, T: PardisoScalar> CsrMatrix<'a, T> {
+    // Copies of ia and ja shifted to one-based indexing, or None if
+    // the matrix is one-based already.  Used by backends that do not
+    // accept zero-based input
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn one_based_indices(&self) -> Option<(Vec<i32>, Vec<i32>)> {
+        match self.base {
+            IndexBase::One => None,
+            IndexBase::Zero => {
+                let ia = self.ia.iter().map(|&i| i + 1).collect();
+                let ja = self.ja.iter().map(|&j| j + 1).collect();
+                Some((ia, ja))
+            }
+        }
+    }
+
+    // Whether (ia, ja) is the structure of this zero-based matrix
+    // shifted to one-based indexing, i.e. a copy that can be reused
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn has_one_based_copy(&self, (ia, ja): &(Vec<i32>, Vec<i32>)) -> bool {
+        let shifted = |copy: &[i32], indices: &[i32]| {
+            copy.len() == indices.len() && copy.iter().zip(indices).all(|(&c, &i)| c == i + 1)
+        };
+        self.base == IndexBase::Zero && shifted(ia, self.ia) && shifted(ja, self.ja)
+    }
+
+    // Same values with a rebased copy of the structure, as returned
+    // by one_based_indices
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn with_indices<'b>(&self, ia: &'b [i32], ja: &'b [i32]) -> CsrMatrix<'b, T>
+    where
+        'a: 'b,
+    {
+        debug_assert!(ia.len() == self.ia.len() && ja.len() == self.ja.len());
+        CsrMatrix {
+            n: self.n,
+            base: if ia[0] == 0 {
+                IndexBase::Zero
+            } else {
+                IndexBase::One
+            },
+            a: self.a,
+            ia,
+            ja,
+        }
+    }
+}
+
+impl CsrMatrix<'static> {
+    // dummy matrix used when only releasing solver resources
+    pub(crate) fn empty() -> Self {
+        CsrMatrix {
+            n: 0,
+            base: IndexBase::One,
+            a: &[],
+            ia: &[1],
+            ja: &[],
+        }
+    }
+}
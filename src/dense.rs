@@ -0,0 +1,109 @@
+use crate::{CsrMatrix, PardisoError, PardisoInterface, PardisoScalar, Phase};
+
+/// A dense matrix stored in row-major order.
+///
+/// Used for results that Pardiso returns in dense form, such as the Schur
+/// complement.  Entry `(i, j)` is stored at `data[i * ncols + j]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenseMatrix<T: PardisoScalar = f64> {
+    nrows: usize,
+    ncols: usize,
+    data: Vec<T>,
+}
+
+impl<T: PardisoScalar> DenseMatrix<T> {
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn from_row_major(nrows: usize, ncols: usize, data: Vec<T>) -> Self {
+        debug_assert_eq!(data.len(), nrows * ncols);
+        Self { nrows, ncols, data }
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+    pub fn into_vec(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T: PardisoScalar> std::ops::Index<(usize, usize)> for DenseMatrix<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.nrows && j < self.ncols);
+        &self.data[i * self.ncols + j]
+    }
+}
+
+// Checks that indices into a vector of length n are in range and
+// distinct
+pub(crate) fn check_indices(n: usize, indices: &[usize]) -> Result<(), PardisoError> {
+    let mut seen = vec![false; n];
+    for &i in indices {
+        if i >= n || seen[i] {
+            return Err(PardisoError::InvalidSparseIndex { index: i });
+        }
+        seen[i] = true;
+    }
+    Ok(())
+}
+
+// Dense vector of length n with the given nonzeros
+pub(crate) fn scatter(
+    n: usize,
+    indices: &[usize],
+    values: &[f64],
+) -> Result<Vec<f64>, PardisoError> {
+    if indices.len() != values.len() {
+        return Err(PardisoError::RhsLength {
+            expected: indices.len(),
+            found: values.len(),
+        });
+    }
+    check_indices(n, indices)?;
+    let mut v = vec![0.0; n];
+    indices.iter().zip(values).for_each(|(&i, &x)| v[i] = x);
+    Ok(v)
+}
+
+// Sparse solve through a dense one, returning the components of x at
+// wanted_indices.  The matrix must have been factorized
+pub(crate) fn solve_sparse_dense<P: PardisoInterface + ?Sized>(
+    ps: &mut P,
+    matrix: &CsrMatrix,
+    rhs_indices: &[usize],
+    rhs_values: &[f64],
+    wanted_indices: &[usize],
+) -> Result<Vec<f64>, PardisoError> {
+    let n = matrix.n();
+    ps.data().check_factored()?;
+    let mut b = scatter(n, rhs_indices, rhs_values)?;
+    check_indices(n, wanted_indices)?;
+
+    let mut x = vec![0.0; n];
+    ps.set_phase(Phase::SolveIterativeRefine);
+    ps.pardiso(matrix, &mut b, &mut x, 1)?;
+
+    let x = if ps.data().iparm[5] == 1 { b } else { x };
+    Ok(wanted_indices.iter().map(|&i| x[i]).collect())
+}
+
+// Sorted copy of the row/column indices of a Schur complement block
+// in an n x n matrix.  Row and column i of the complement correspond
+// to the i-th smallest block index
+#[allow(dead_code)] // if no features are set
+pub(crate) fn schur_block(n: usize, indices: &[usize]) -> Result<Vec<usize>, PardisoError> {
+    let mut block = indices.to_vec();
+    block.sort_unstable();
+    let repeated = block.windows(2).any(|w| w[0] == w[1]);
+    let in_range = block.last().is_some_and(|&i| i < n);
+    if repeated || !in_range {
+        return Err(PardisoError::InvalidSchurBlock);
+    }
+    Ok(block)
+}
@@ -1,5 +1,7 @@
+use num_enum::TryFromPrimitive;
+
 #[repr(i32)]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, TryFromPrimitive)]
 pub enum MatrixType {
     RealStructurallySymmetric = 1,
     RealSymmetricPositiveDefinite = 2,
@@ -62,7 +64,7 @@ impl std::fmt::Display for MatrixType {
 }
 
 #[repr(i32)]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Phase {
     #[default]
     Analysis = 11,
@@ -79,6 +81,30 @@ pub enum Phase {
     ReleaseAll = -1,
 }
 
+impl Phase {
+    pub fn includes_solve(&self) -> bool {
+        matches!(
+            self,
+            Phase::AnalysisNumFactSolveRefine
+                | Phase::NumFactSolveRefine
+                | Phase::SolveIterativeRefine
+                | Phase::SolveIterativeRefineOnlyForward
+                | Phase::SolveIterativeRefineOnlyDiag
+                | Phase::SolveIterativeRefineOnlyBackward
+        )
+    }
+
+    pub fn includes_factorization(&self) -> bool {
+        matches!(
+            self,
+            Phase::AnalysisNumFact
+                | Phase::AnalysisNumFactSolveRefine
+                | Phase::NumFact
+                | Phase::NumFactSolveRefine
+        )
+    }
+}
+
 impl std::fmt::Display for Phase {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name = match self {
@@ -116,3 +142,120 @@ pub enum SolverType {
     Direct = 0,
     Iterative = 1,
 }
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IndexBase {
+    Zero = 0,
+    #[default]
+    One = 1,
+}
+
+impl std::fmt::Display for IndexBase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            IndexBase::Zero => "Zero-based",
+            IndexBase::One => "One-based",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Precision {
+    #[default]
+    Double,
+    Single,
+}
+
+impl std::fmt::Display for Precision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Precision::Double => "Double",
+            Precision::Single => "Single",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ordering {
+    // (approximate) minimum degree
+    Amd,
+    // nested dissection from METIS
+    #[default]
+    Metis,
+    // nested dissection from parallel (OpenMP) METIS
+    ParallelMetis,
+    // user supplied permutation, see PardisoInterface::set_perm
+    User,
+}
+
+impl std::fmt::Display for Ordering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Ordering::Amd => "Minimum Degree",
+            Ordering::Metis => "METIS",
+            Ordering::ParallelMetis => "Parallel METIS",
+            Ordering::User => "User",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Pivoting {
+    // 1x1 diagonal pivoting
+    Diagonal,
+    // 1x1 and 2x2 Bunch-Kaufman pivoting
+    #[default]
+    BunchKaufman,
+}
+
+impl std::fmt::Display for Pivoting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Pivoting::Diagonal => "Diagonal",
+            Pivoting::BunchKaufman => "Bunch-Kaufman",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Transpose {
+    #[default]
+    None,
+    Transpose,
+    ConjugateTranspose,
+}
+
+impl std::fmt::Display for Transpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Transpose::None => "None",
+            Transpose::Transpose => "Transpose",
+            Transpose::ConjugateTranspose => "Conjugate Transpose",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    Negative = -1,
+    Zero = 0,
+    Positive = 1,
+}
+
+impl std::fmt::Display for Sign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Sign::Negative => "Negative",
+            Sign::Zero => "Zero",
+            Sign::Positive => "Positive",
+        };
+        write!(f, "{name}")
+    }
+}
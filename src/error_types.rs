@@ -1,3 +1,4 @@
+use crate::{MatrixType, Precision};
 use num_enum::{FromPrimitive, IntoPrimitive};
 use thiserror::Error;
 
@@ -88,6 +89,75 @@ pub enum PardisoError {
     MKL(#[from] MKLPardisoError),
     #[error("PANUA ERROR: {0}")]
     Panua(#[from] PanuaPardisoError),
+    // structural defects in CSR matrix data
+    #[error("Row pointer array has length {found}, expected {expected}.")]
+    RowPointerLength { expected: usize, found: usize },
+    #[error("Column index array has length {found}, expected {expected}.")]
+    ColumnIndexLength { expected: usize, found: usize },
+    #[error("Value array has length {found}, expected {expected}.")]
+    ValuesLength { expected: usize, found: usize },
+    #[error("Row pointers are not monotone at row {row}.")]
+    RowPointerNotMonotone { row: usize },
+    #[error("Column index {index} in row {row} is out of range.")]
+    ColumnIndexOutOfRange { row: usize, index: i64 },
+    #[error("Invalid index base {0}.  Row pointers must start at 0 or 1.")]
+    InvalidIndexBase(i64),
+    #[error("Matrix dimension {0} exceeds the range of the index type.")]
+    DimensionOverflow(usize),
+    // right-hand side and solution buffer defects
+    #[error("Invalid number of right-hand sides {0}.")]
+    InvalidNumRhs(i32),
+    #[error("Right-hand side buffer has length {found}, expected at least {expected}.")]
+    RhsLength { expected: usize, found: usize },
+    #[error("Solution buffer has length {found}, expected at least {expected}.")]
+    SolutionLength { expected: usize, found: usize },
+    #[error("Right-hand side buffer has length {found}, expected a positive multiple of {n}.")]
+    RhsNotMultiple { n: usize, found: usize },
+    #[error("Matrix type {mtype} is incompatible with {scalar} data.")]
+    ScalarTypeMismatch {
+        mtype: MatrixType,
+        scalar: &'static str,
+    },
+    #[error("Index width does not match the width used to create the solver handle.")]
+    IndexWidthMismatch,
+    #[error(
+        "{found} precision data cannot be used with a solver analyzed in {expected} precision."
+    )]
+    PrecisionMismatch {
+        expected: Precision,
+        found: Precision,
+    },
+    #[error("{option} is not supported by the {backend} backend.")]
+    UnsupportedOption {
+        backend: &'static str,
+        option: String,
+    },
+    #[error("Permutation has length {found}, expected {expected}.")]
+    PermutationLength { expected: usize, found: usize },
+    #[error("Invalid permutation entry {value} at position {index}.")]
+    InvalidPermutation { index: usize, value: i64 },
+    #[error("Inertia is undefined for matrix type {0}.")]
+    InertiaUndefined(MatrixType),
+    #[error("Invalid maximum number of factorizations {0}.")]
+    InvalidMaxfct(i32),
+    #[error("No free factorization slot (maxfct = {maxfct}).")]
+    FactorizationSetFull { maxfct: i32 },
+    #[error("No numerical factorization is available for matrix number {mnum}.")]
+    NotFactorized { mnum: i32 },
+    #[error("Schur complement block indices must be distinct, in range and nonempty.")]
+    InvalidSchurBlock,
+    #[error("The backend did not compute the Schur complement of order {0}.")]
+    SchurNotComputed(usize),
+    #[error("Sparse vector index {index} is out of range or repeated.")]
+    InvalidSparseIndex { index: usize },
+    #[error("Partial solves require {0}.")]
+    PartialSolveUnavailable(&'static str),
+    #[error("Invalid saved solver state: {0}.")]
+    InvalidSavedState(String),
+    #[error("A pivot handler is already installed by another solver.")]
+    PivotHandlerInUse,
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("Unknown error.")]
     Unknown,
 }
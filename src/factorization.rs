@@ -0,0 +1,338 @@
+use crate::{
+    CsrMatrix, MatrixType, PardisoError, PardisoInterface, PardisoScalar, Phase, Transpose,
+};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+/// Typestate wrapper around [`PardisoInterface`].
+///
+/// A [`Solver`] must be analyzed before it can be factorized, and factorized
+/// before it can solve.  Each step consumes the previous state, so calling
+/// the phases out of order is a compile error rather than a runtime failure
+/// inside the Pardiso library:
+///
+/// ```rust, compile_fail
+/// use pardiso_wrapper::*;
+/// fn solve_too_early<P: PardisoInterface>(solver: Solver<P>, b: &[f64]) {
+///     let _ = solver.solve(b);
+/// }
+/// ```
+///
+/// The analyzed and factored states borrow the matrix, so the sparsity
+/// pattern cannot be freed or modified while the factorization is alive.
+pub struct Solver<P: PardisoInterface> {
+    ps: P,
+}
+
+/// A solver holding the symbolic factorization of a sparsity pattern.
+pub struct Analyzed<'a, P: PardisoInterface, T: PardisoScalar = f64> {
+    ps: P,
+    matrix: CsrMatrix<'a, T>,
+}
+
+/// A solver holding a numeric factorization, ready to solve.
+pub struct Factored<'a, P: PardisoInterface, T: PardisoScalar = f64> {
+    ps: P,
+    matrix: CsrMatrix<'a, T>,
+}
+
+impl<P: PardisoInterface> Solver<P> {
+    // create a backend instance and initialize it for the given matrix type
+    pub fn new(mtype: MatrixType) -> Result<Self, PardisoError> {
+        let mut ps = P::new()?;
+        ps.set_matrix_type(mtype);
+        ps.pardisoinit()?;
+        Ok(Self { ps })
+    }
+
+    // wrap an already configured backend.  The caller is responsible
+    // for having called pardisoinit on it
+    pub fn from_interface(ps: P) -> Self {
+        Self { ps }
+    }
+
+    pub fn interface(&self) -> &P {
+        &self.ps
+    }
+    pub fn interface_mut(&mut self) -> &mut P {
+        &mut self.ps
+    }
+
+    pub fn analyze<'a, T: PardisoScalar>(
+        mut self,
+        matrix: &CsrMatrix<'a, T>,
+    ) -> Result<Analyzed<'a, P, T>, PardisoError> {
+        self.ps.set_phase(Phase::Analysis);
+        T::call_pardiso(&mut self.ps, matrix, &mut [], &mut [], 1)?;
+        Ok(Analyzed {
+            ps: self.ps,
+            matrix: *matrix,
+        })
+    }
+}
+
+impl<'a, P: PardisoInterface, T: PardisoScalar> Analyzed<'a, P, T> {
+    pub fn interface(&self) -> &P {
+        &self.ps
+    }
+    pub fn matrix(&self) -> &CsrMatrix<'a, T> {
+        &self.matrix
+    }
+
+    // numeric factorization of new values on the analyzed pattern
+    pub fn factorize(mut self, values: &'a [T]) -> Result<Factored<'a, P, T>, PardisoError> {
+        let matrix = self.matrix.with_values(values)?;
+        self.ps.set_phase(Phase::NumFact);
+        T::call_pardiso(&mut self.ps, &matrix, &mut [], &mut [], 1)?;
+        Ok(Factored {
+            ps: self.ps,
+            matrix,
+        })
+    }
+}
+
+impl<'a, P: PardisoInterface, T: PardisoScalar> Factored<'a, P, T> {
+    pub fn interface(&self) -> &P {
+        &self.ps
+    }
+    pub fn matrix(&self) -> &CsrMatrix<'a, T> {
+        &self.matrix
+    }
+
+    // drop back to the analyzed state, e.g. to refactor with new values
+    pub fn into_analyzed(self) -> Analyzed<'a, P, T> {
+        Analyzed {
+            ps: self.ps,
+            matrix: self.matrix,
+        }
+    }
+
+    // solve for one or more right-hand sides stored column-wise in b
+    pub fn solve(&mut self, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        self.solve_impl(b, Phase::SolveIterativeRefine, None)
+    }
+
+    // solve with A^T, e.g. for adjoint problems
+    pub fn solve_transpose(&mut self, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        self.solve_impl(b, Phase::SolveIterativeRefine, Some(Transpose::Transpose))
+    }
+
+    // solve with the conjugate transpose A^H
+    pub fn solve_conjugate_transpose(&mut self, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        self.solve_impl(
+            b,
+            Phase::SolveIterativeRefine,
+            Some(Transpose::ConjugateTranspose),
+        )
+    }
+
+    // Partial solves with the factors of A = L D L^T (or A = L U), as
+    // used for preconditioner splitting.  solve_forward applies L^{-1},
+    // solve_diagonal D^{-1} and solve_backward L^{-T} (or U^{-1}), so
+    // applying all three in turn is the same as solve.  Any permutation
+    // and scaling chosen by the solver are included in the forward and
+    // backward steps.  Iterative refinement needs the full solve, so the
+    // backends only apply it in solve and ignore iparm[7] here
+    pub fn solve_forward(&mut self, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        self.check_partial_solve(false)?;
+        self.solve_impl(b, Phase::SolveIterativeRefineOnlyForward, None)
+    }
+    pub fn solve_diagonal(&mut self, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        self.check_partial_solve(true)?;
+        self.solve_impl(b, Phase::SolveIterativeRefineOnlyDiag, None)
+    }
+    pub fn solve_backward(&mut self, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        self.check_partial_solve(false)?;
+        self.solve_impl(b, Phase::SolveIterativeRefineOnlyBackward, None)
+    }
+
+    fn check_partial_solve(&self, diagonal: bool) -> Result<(), PardisoError> {
+        let mtype = self.ps.get_matrix_type();
+        if diagonal && !(mtype.is_symmetric() || mtype.is_hermitian()) {
+            return Err(PardisoError::PartialSolveUnavailable(
+                "a symmetric or Hermitian matrix type for the diagonal step",
+            ));
+        }
+        Ok(())
+    }
+
+    // Without an explicit transpose the solve uses the iparm settings
+    // of the interface.  Transposed solves are always full solves
+    fn solve_impl(
+        &mut self,
+        b: &[T],
+        phase: Phase,
+        transpose: Option<Transpose>,
+    ) -> Result<Vec<T>, PardisoError> {
+        let n = self.matrix.n();
+        if n == 0 {
+            return Ok(vec![]);
+        }
+        let (mut b, mut x, nrhs) = rhs_buffers(n, b)?;
+
+        match transpose {
+            Some(transpose) => {
+                let ps = &mut self.ps;
+                ps.solve_with_transpose(transpose, &self.matrix, &mut b, &mut x, nrhs)?;
+            }
+            None => {
+                self.ps.set_phase(phase);
+                T::call_pardiso(&mut self.ps, &self.matrix, &mut b, &mut x, nrhs)?;
+            }
+        }
+
+        Ok(solution(&self.ps, b, x))
+    }
+}
+
+/// Several numerical factorizations of one analyzed sparsity pattern.
+///
+/// Pardiso can hold up to `maxfct` factorizations of matrices with the
+/// same pattern in one handle, numbered by `mnum`.  A set is created with
+/// [`Solver::analyze_set`], after which each call to
+/// [`factorize`](FactorizationSet::factorize) stores a factorization of new
+/// values (e.g. for different time steps or shifts) and returns a
+/// [`FactorHandle`] used to solve with it.  Releasing a handle frees its
+/// slot for another factorization.
+pub struct FactorizationSet<'a, P: PardisoInterface, T: PardisoScalar = f64> {
+    ps: P,
+    pattern: CsrMatrix<'a, T>,
+    // factorized values and handle id for each mnum - 1
+    slots: Vec<Option<(&'a [T], u64)>>,
+}
+
+// handle ids are unique across all sets
+static FACTOR_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Handle to one factorization in a [`FactorizationSet`].
+///
+/// A handle is invalidated when it is released, and cannot be confused
+/// with a later factorization stored in the same slot or with a
+/// factorization in another set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FactorHandle {
+    mnum: i32,
+    id: u64,
+}
+
+impl FactorHandle {
+    pub fn mnum(&self) -> i32 {
+        self.mnum
+    }
+}
+
+impl<P: PardisoInterface> Solver<P> {
+    // symbolic factorization shared by up to maxfct numeric factorizations
+    pub fn analyze_set<'a, T: PardisoScalar>(
+        mut self,
+        matrix: &CsrMatrix<'a, T>,
+        maxfct: i32,
+    ) -> Result<FactorizationSet<'a, P, T>, PardisoError> {
+        if maxfct < 1 {
+            return Err(PardisoError::InvalidMaxfct(maxfct));
+        }
+        self.ps.set_maxfct(maxfct);
+        self.ps.set_mnum(1);
+        self.ps.set_phase(Phase::Analysis);
+        T::call_pardiso(&mut self.ps, matrix, &mut [], &mut [], 1)?;
+        Ok(FactorizationSet {
+            ps: self.ps,
+            pattern: *matrix,
+            slots: vec![None; maxfct as usize],
+        })
+    }
+}
+
+impl<'a, P: PardisoInterface, T: PardisoScalar> FactorizationSet<'a, P, T> {
+    pub fn interface(&self) -> &P {
+        &self.ps
+    }
+    pub fn pattern(&self) -> &CsrMatrix<'a, T> {
+        &self.pattern
+    }
+    pub fn maxfct(&self) -> i32 {
+        self.slots.len() as i32
+    }
+    // number of stored factorizations
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // numeric factorization of new values on the analyzed pattern,
+    // stored in the first free slot
+    pub fn factorize(&mut self, values: &'a [T]) -> Result<FactorHandle, PardisoError> {
+        let slot = self.slots.iter().position(Option::is_none).ok_or(
+            PardisoError::FactorizationSetFull {
+                maxfct: self.maxfct(),
+            },
+        )?;
+        let matrix = self.pattern.with_values(values)?;
+        let mnum = slot as i32 + 1;
+
+        self.ps.set_mnum(mnum);
+        self.ps.set_phase(Phase::NumFact);
+        T::call_pardiso(&mut self.ps, &matrix, &mut [], &mut [], 1)?;
+
+        let id = FACTOR_COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+        self.slots[slot] = Some((values, id));
+        Ok(FactorHandle { mnum, id })
+    }
+
+    // solve with the factorization of a handle, for one or more
+    // right-hand sides stored column-wise in b
+    pub fn solve(&mut self, handle: FactorHandle, b: &[T]) -> Result<Vec<T>, PardisoError> {
+        let values = self.values(handle)?;
+        let matrix = self.pattern.with_values(values)?;
+        if matrix.n() == 0 {
+            return Ok(vec![]);
+        }
+        let (mut b, mut x, nrhs) = rhs_buffers(matrix.n(), b)?;
+
+        self.ps.set_mnum(handle.mnum);
+        self.ps.set_phase(Phase::SolveIterativeRefine);
+        T::call_pardiso(&mut self.ps, &matrix, &mut b, &mut x, nrhs)?;
+        Ok(solution(&self.ps, b, x))
+    }
+
+    // release the factorization of a handle (Phase::ReleaseLUandMNUM)
+    pub fn release(&mut self, handle: FactorHandle) -> Result<(), PardisoError> {
+        let values = self.values(handle)?;
+        let matrix = self.pattern.with_values(values)?;
+
+        self.ps.set_mnum(handle.mnum);
+        self.ps.set_phase(Phase::ReleaseLUandMNUM);
+        T::call_pardiso(&mut self.ps, &matrix, &mut [], &mut [], 1)?;
+        self.slots[handle.mnum as usize - 1] = None;
+        Ok(())
+    }
+
+    fn values(&self, handle: FactorHandle) -> Result<&'a [T], PardisoError> {
+        match self.slots.get(handle.mnum as usize - 1) {
+            Some(Some((values, id))) if *id == handle.id => Ok(values),
+            _ => Err(PardisoError::NotFactorized { mnum: handle.mnum }),
+        }
+    }
+}
+
+// Copies of b and a solution buffer for one or more right-hand sides
+// stored column-wise in b, so the length of b must be a multiple of n.
+// Pardiso may overwrite b, so it is copied
+fn rhs_buffers<T: PardisoScalar>(n: usize, b: &[T]) -> Result<(Vec<T>, Vec<T>, i32), PardisoError> {
+    let nrhs = b.len() / n;
+    if nrhs == 0 || b.len() % n != 0 {
+        return Err(PardisoError::RhsNotMultiple { n, found: b.len() });
+    }
+    let nrhs = i32::try_from(nrhs).map_err(|_| PardisoError::DimensionOverflow(b.len()))?;
+    Ok((b.to_vec(), vec![T::default(); b.len()], nrhs))
+}
+
+// the solution is written to b when solving in place
+fn solution<P: PardisoInterface, T>(ps: &P, b: Vec<T>, x: Vec<T>) -> Vec<T> {
+    if ps.get_iparm(5) == 1 {
+        b
+    } else {
+        x
+    }
+}
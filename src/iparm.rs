@@ -0,0 +1,111 @@
+use crate::{Ordering, Pivoting, Transpose};
+
+/// Typed view of the commonly used `iparm` options.
+///
+/// Entries are numbered and encoded differently by MKL and Panua, so each
+/// backend translates this struct into its own `iparm` array.  There is
+/// no default, since the defaults chosen by `pardisoinit` depend on the
+/// matrix type.  Instead read the current options, modify them and write
+/// them back:
+///
+/// ```rust, ignore
+/// let options = ps.get_iparm_options().ordering(Ordering::Amd).scaling(true);
+/// ps.set_iparm_options(&options)?;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IParm {
+    // fill-in reducing ordering
+    pub ordering: Ordering,
+    // maximum number of iterative refinement steps
+    pub refinement_steps: i32,
+    // small pivots are perturbed to 10^(-pivot_perturbation)
+    pub pivot_perturbation: i32,
+    // nonsymmetric permutation and scaling
+    pub scaling: bool,
+    // maximum weighted matching for symmetric indefinite matrices
+    pub weighted_matching: bool,
+    // pivoting for symmetric indefinite matrices
+    pub pivoting: Pivoting,
+    // solve with A, A^T or A^H
+    pub transpose: Transpose,
+    // write the solution to b instead of x
+    pub solve_in_place: bool,
+    // check the matrix structure before use
+    pub matrix_checker: bool,
+}
+
+impl IParm {
+    pub fn ordering(mut self, ordering: Ordering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+    pub fn refinement_steps(mut self, steps: i32) -> Self {
+        self.refinement_steps = steps;
+        self
+    }
+    pub fn pivot_perturbation(mut self, exponent: i32) -> Self {
+        self.pivot_perturbation = exponent;
+        self
+    }
+    pub fn scaling(mut self, scaling: bool) -> Self {
+        self.scaling = scaling;
+        self
+    }
+    pub fn weighted_matching(mut self, matching: bool) -> Self {
+        self.weighted_matching = matching;
+        self
+    }
+    pub fn pivoting(mut self, pivoting: Pivoting) -> Self {
+        self.pivoting = pivoting;
+        self
+    }
+    pub fn transpose(mut self, transpose: Transpose) -> Self {
+        self.transpose = transpose;
+        self
+    }
+    pub fn solve_in_place(mut self, in_place: bool) -> Self {
+        self.solve_in_place = in_place;
+        self
+    }
+    pub fn matrix_checker(mut self, check: bool) -> Self {
+        self.matrix_checker = check;
+        self
+    }
+
+    // Writes the entries that MKL and Panua number and encode the same
+    // way.  Backend specific entries are written by the backends, which
+    // also initialize the remaining entries first
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn write_common(&self, iparm: &mut [i32; 64]) {
+        iparm[5] = self.solve_in_place as i32;
+        iparm[7] = self.refinement_steps;
+        iparm[9] = self.pivot_perturbation;
+        iparm[10] = self.scaling as i32;
+        iparm[12] = self.weighted_matching as i32;
+        iparm[20] = match self.pivoting {
+            Pivoting::Diagonal => 0,
+            Pivoting::BunchKaufman => 1,
+        };
+        iparm[26] = self.matrix_checker as i32;
+    }
+
+    // Reads the common entries.  The ordering and transpose flag are
+    // encoded differently, so they are decoded by the backends
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn read_common(iparm: &[i32; 64], ordering: Ordering, transpose: Transpose) -> Self {
+        Self {
+            ordering,
+            transpose,
+            solve_in_place: iparm[5] != 0,
+            refinement_steps: iparm[7],
+            pivot_perturbation: iparm[9],
+            scaling: iparm[10] != 0,
+            weighted_matching: iparm[12] != 0,
+            pivoting: match iparm[20] {
+                0 => Pivoting::Diagonal,
+                _ => Pivoting::BunchKaufman,
+            },
+            matrix_checker: iparm[26] != 0,
+        }
+    }
+}
@@ -7,6 +7,8 @@
 //!
 //! - `mkl`: Intel MKL implementation (x86_64 only)
 //! - `panua`: Panua implementation
+//! - `mkl-pivot-hook`: exports the `mkl_pardiso_pivot` hook for custom pivot
+//!   replacement in MKL, see `MKLPardisoSolver::set_pivot_handler`
 //!
 //! Both options are supported via the common [`PardisoInterface`] trait.
 //!
@@ -28,25 +30,43 @@
 //! Panua Pardiso is a commercial solver and requires a separate license.
 //!
 //! ## Example
-//! ```rust, no_test
+//! ```rust, ignore
 #![doc = include_str!("../examples/symmetric.rs")]
 //! ```
 
+mod csr;
+pub use csr::*;
+mod dense;
+pub use dense::*;
 mod enums;
 pub use enums::*;
 mod error_types;
 pub use error_types::*;
+mod factorization;
+pub use factorization::*;
+mod iparm;
+pub use iparm::*;
+mod permutation;
+pub use permutation::*;
+mod scalar;
+pub use scalar::*;
+mod stats;
+pub use stats::*;
+
+pub use num_complex::Complex64;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "panua")]{
         mod panua;
-        pub use panua::PanuaPardisoSolver;
+        pub use panua::{DParm, PanuaPardisoSolver, SelectedInverse};
 }}
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "mkl")]{
         mod mkl;
-        pub use mkl::MKLPardisoSolver;
+        pub use mkl::{MKLPardisoSolver, OutOfCoreConfig, OutOfCoreMode};
+        #[cfg(feature = "mkl-pivot-hook")]
+        pub use mkl::PivotHandler;
 }}
 
 #[cfg(test)]
@@ -64,6 +84,14 @@ pub struct PardisoData {
     pub maxfct: i32,
     pub mnum: i32,
     pub perm: Vec<i32>,
+    pub perm_base: IndexBase,
+    // precision of the current handle, fixed at analysis
+    pub precision: Option<Precision>,
+    // dimension of the matrix of the most recent call
+    pub n: usize,
+    // matrix numbers (mnum) with a current numerical factorization,
+    // and the inertia reported by that factorization
+    pub factored: std::collections::BTreeMap<i32, Inertia>,
 }
 
 impl Default for PardisoData {
@@ -78,10 +106,176 @@ impl Default for PardisoData {
             maxfct: 1,
             mnum: 1,
             perm: vec![],
+            perm_base: IndexBase::One,
+            precision: None,
+            n: 0,
+            factored: std::collections::BTreeMap::new(),
         }
     }
 }
 
+impl PardisoData {
+    // Checks the right-hand side and solution buffers against the
+    // current phase.  Analysis, factorization and release phases do
+    // not touch b or x.  Solve phases read n*nrhs entries of b and write
+    // n*nrhs entries of x.  With an in-place solve (iparm[5] = 1) the
+    // solution is written to b instead, but x is still used as workspace
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn check_buffers<T>(
+        &self,
+        n: usize,
+        nrhs: i32,
+        b: &[T],
+        x: &[T],
+    ) -> Result<(), PardisoError> {
+        if !self.phase.includes_solve() {
+            return Ok(());
+        }
+        if nrhs < 1 {
+            return Err(PardisoError::InvalidNumRhs(nrhs));
+        }
+        let expected = n
+            .checked_mul(nrhs as usize)
+            .ok_or(PardisoError::DimensionOverflow(n))?;
+
+        if b.len() < expected {
+            return Err(PardisoError::RhsLength {
+                expected,
+                found: b.len(),
+            });
+        }
+        if x.len() < expected {
+            return Err(PardisoError::SolutionLength {
+                expected,
+                found: x.len(),
+            });
+        }
+        Ok(())
+    }
+
+    // Checks that the scalar type agrees with the configured matrix
+    // type.  Release phases do not touch any data and are always allowed
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn check_scalar<T: PardisoScalar>(&self) -> Result<(), PardisoError> {
+        if matches!(self.phase, Phase::ReleaseAll | Phase::ReleaseLUandMNUM) {
+            return Ok(());
+        }
+        if self.mtype.is_complex() != T::IS_COMPLEX {
+            return Err(PardisoError::ScalarTypeMismatch {
+                mtype: self.mtype,
+                scalar: T::NAME,
+            });
+        }
+        Ok(())
+    }
+
+    // Checks that the data precision agrees with the precision used
+    // to create the current handle, if any
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn check_precision(&self, precision: Precision) -> Result<(), PardisoError> {
+        if matches!(self.phase, Phase::ReleaseAll | Phase::ReleaseLUandMNUM) {
+            return Ok(());
+        }
+        match self.precision {
+            Some(expected) if expected != precision => Err(PardisoError::PrecisionMismatch {
+                expected,
+                found: precision,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    // Prepares the perm buffer for a call on an n x n matrix with the
+    // given index base.  A user permutation (iparm[4] = 1) must have
+    // length n and is shifted to the base of the matrix.  When Pardiso
+    // returns its permutation (iparm[4] = 2) the buffer is resized to n
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn prepare_perm(&mut self, n: usize, base: IndexBase) -> Result<(), PardisoError> {
+        if matches!(self.phase, Phase::ReleaseAll | Phase::ReleaseLUandMNUM) {
+            return Ok(());
+        }
+        match self.iparm[4] {
+            1 => {
+                if self.perm.len() != n {
+                    return Err(PardisoError::PermutationLength {
+                        expected: n,
+                        found: self.perm.len(),
+                    });
+                }
+                self.rebase_perm(base);
+            }
+            2 => {
+                self.perm.resize(n, 0);
+                self.perm_base = base;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Shifts the perm buffer to the given index base
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn rebase_perm(&mut self, base: IndexBase) {
+        if self.perm_base != base {
+            let shift = base as i32 - self.perm_base as i32;
+            self.perm.iter_mut().for_each(|p| *p += shift);
+            self.perm_base = base;
+        }
+    }
+
+    // Updates the handle state after a successful call on an n x n
+    // matrix.  A new analysis invalidates the factors of all matrix
+    // numbers
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn record_call(&mut self, precision: Precision, n: usize) {
+        match self.phase {
+            Phase::ReleaseAll => {
+                self.precision = None;
+                self.factored.clear();
+            }
+            Phase::ReleaseLUandMNUM => {
+                self.factored.remove(&self.mnum);
+            }
+            phase => {
+                self.precision = Some(precision);
+                self.n = n;
+                if phase == Phase::Analysis {
+                    self.factored.clear();
+                } else if phase.includes_factorization() {
+                    let inertia = self.inertia(n);
+                    self.factored.insert(self.mnum, inertia);
+                }
+            }
+        }
+    }
+
+    // Inertia as reported in iparm by a factorization.  Positive
+    // definite types do not report it, but are known to have only
+    // positive eigenvalues if the factorization succeeded
+    fn inertia(&self, n: usize) -> Inertia {
+        let (positive, negative) = match self.mtype {
+            MatrixType::RealSymmetricPositiveDefinite
+            | MatrixType::ComplexHermitianPositiveDefinite => (n as i32, 0),
+            _ => (self.iparm[21], self.iparm[22]),
+        };
+        Inertia {
+            positive,
+            negative,
+            zero: n as i32 - positive - negative,
+            perturbed_pivots: self.iparm[13],
+        }
+    }
+
+    // Checks that the current matrix number has been factorized
+    #[allow(dead_code)] // if no features are set
+    pub(crate) fn check_factored(&self) -> Result<(), PardisoError> {
+        if !self.factored.contains_key(&self.mnum) {
+            return Err(PardisoError::NotFactorized { mnum: self.mnum });
+        }
+        Ok(())
+    }
+}
+
 pub trait PardisoInterface {
     // Getters and Setters (default implementations)
 
@@ -124,10 +318,36 @@ pub trait PardisoInterface {
     fn get_perm(&self) -> &[i32] {
         self.data().perm.as_slice()
     }
-    // : Problem here I think.   Should not own the vector passed
-    fn set_perm(&mut self, perm: &[i32]) {
-        self.data_mut().perm.resize(perm.len(), 0_i32);
-        self.data_mut().perm.copy_from_slice(perm);
+    // use perm as the fill-in reducing ordering (Ordering::User)
+    fn set_perm(&mut self, perm: Permutation) -> Result<(), PardisoError> {
+        self.set_ordering(Ordering::User)?;
+        let data = self.data_mut();
+        data.perm_base = perm.base();
+        data.perm = perm.into_vec();
+        Ok(())
+    }
+    // return the ordering computed during Phase::Analysis, to be
+    // read with get_permutation.  Not compatible with a user ordering
+    fn set_return_perm(&mut self, enable: bool) -> Result<(), PardisoError> {
+        self.ensure_iparm_initialized()?;
+        let iparm = &mut self.data_mut().iparm;
+        if enable {
+            iparm[4] = 2;
+        } else if iparm[4] == 2 {
+            iparm[4] = 0;
+        }
+        Ok(())
+    }
+    // the user supplied or returned ordering, if any
+    fn get_permutation(&self) -> Option<Permutation> {
+        let data = self.data();
+        match data.iparm[4] {
+            1 | 2 if !data.perm.is_empty() => Some(Permutation::new_unchecked(
+                data.perm.clone(),
+                data.perm_base,
+            )),
+            _ => None,
+        }
     }
     fn get_iparm(&self, i: usize) -> i32 {
         self.data().iparm[i]
@@ -145,6 +365,17 @@ pub trait PardisoInterface {
         self.data().iparm[22]
     }
 
+    // Inertia from the last numerical factorization of the current
+    // matrix number.  Only defined for symmetric or Hermitian types
+    fn get_inertia(&self) -> Result<Inertia, PardisoError> {
+        let data = self.data();
+        if !data.mtype.is_hermitian() {
+            return Err(PardisoError::InertiaUndefined(data.mtype));
+        }
+        data.check_factored()?;
+        Ok(data.factored[&data.mnum])
+    }
+
     // NB: implementors should also implement Drop and call
     // release() in the drop method.
     fn release(&mut self) {
@@ -152,14 +383,12 @@ pub trait PardisoInterface {
         self.set_phase(crate::Phase::ReleaseAll);
 
         // call with dummies since we are releasing resource only
-        let a: Vec<f64> = vec![];
-        let ia: Vec<i32> = vec![];
-        let ja: Vec<i32> = vec![];
+        let matrix = CsrMatrix::empty();
         let mut b: Vec<f64> = vec![];
         let mut x: Vec<f64> = vec![];
 
         // Call Pardiso to release resources, ignoring any errors
-        let _ = self.pardiso(&a, &ia, &ja, &mut b, &mut x, 0, 0);
+        let _ = self.pardiso(&matrix, &mut b, &mut x, 0);
     }
 
     // MKL/Panua specific functions
@@ -167,24 +396,126 @@ pub trait PardisoInterface {
     fn data(&self) -> &PardisoData;
     #[doc(hidden)]
     fn data_mut(&mut self) -> &mut PardisoData;
+    // Fills in the backend defaults if iparm has not been initialized
+    // (iparm[0] = 0) and sets iparm[0] = 1.  Called before changing
+    // individual entries, so that the other entries keep their defaults
+    #[doc(hidden)]
+    fn ensure_iparm_initialized(&mut self) -> Result<(), PardisoError>;
 
     fn name(&self) -> &'static str;
 
+    // fill-in reducing ordering.  Orderings the backend does not
+    // support are rejected rather than silently ignored
+    fn set_ordering(&mut self, ordering: Ordering) -> Result<(), PardisoError>;
+    fn get_ordering(&self) -> Ordering;
+
+    // typed iparm options, translated to and from the backend's own
+    // iparm numbering.  Read back after pardisoinit to get the defaults
+    fn set_iparm_options(&mut self, options: &IParm) -> Result<(), PardisoError>;
+    fn get_iparm_options(&self) -> IParm;
+
+    // iparm[11] value selecting a solve with A, A^T or A^H
+    fn transpose_code(&self, transpose: Transpose) -> i32;
+
+    // Solves with A, A^T or A^H using the current factorization.  The
+    // transpose flag (iparm[11]) is only set for this call and restored
+    // afterwards
+    fn solve_with_transpose<T: PardisoScalar>(
+        &mut self,
+        transpose: Transpose,
+        matrix: &CsrMatrix<T>,
+        b: &mut [T],
+        x: &mut [T],
+        nrhs: i32,
+    ) -> Result<(), PardisoError>
+    where
+        Self: Sized,
+    {
+        self.ensure_iparm_initialized()?;
+        let saved = self.get_iparm(11);
+        let code = self.transpose_code(transpose);
+        self.set_iparm(11, code);
+        self.set_phase(Phase::SolveIterativeRefine);
+        let result = T::call_pardiso(self, matrix, b, x, nrhs);
+        self.set_iparm(11, saved);
+        result
+    }
+    fn solve_transpose<T: PardisoScalar>(
+        &mut self,
+        matrix: &CsrMatrix<T>,
+        b: &mut [T],
+        x: &mut [T],
+        nrhs: i32,
+    ) -> Result<(), PardisoError>
+    where
+        Self: Sized,
+    {
+        self.solve_with_transpose(Transpose::Transpose, matrix, b, x, nrhs)
+    }
+    fn solve_conjugate_transpose<T: PardisoScalar>(
+        &mut self,
+        matrix: &CsrMatrix<T>,
+        b: &mut [T],
+        x: &mut [T],
+        nrhs: i32,
+    ) -> Result<(), PardisoError>
+    where
+        Self: Sized,
+    {
+        self.solve_with_transpose(Transpose::ConjugateTranspose, matrix, b, x, nrhs)
+    }
+
+    // statistics reported by the most recent call to pardiso
+    fn get_stats(&self) -> SolverStats;
+
+    // Solves A x = b for a sparse b with nonzeros rhs_values at
+    // rhs_indices, returning the components of x at wanted_indices.
+    // The matrix must have been factorized.  The default implementation
+    // performs a dense solve, backends with native support override it
+    fn solve_sparse(
+        &mut self,
+        matrix: &CsrMatrix,
+        rhs_indices: &[usize],
+        rhs_values: &[f64],
+        wanted_indices: &[usize],
+    ) -> Result<Vec<f64>, PardisoError> {
+        crate::dense::solve_sparse_dense(self, matrix, rhs_indices, rhs_values, wanted_indices)
+    }
+
+    // natural logarithm of |det(A)| and the sign of det(A) for the
+    // current numerical factorization
+    fn log_determinant(&self) -> Result<(f64, Sign), PardisoError>;
+
     fn new() -> Result<Self, PardisoError>
     where
         Self: Sized;
 
     fn pardisoinit(&mut self) -> Result<(), PardisoError>;
 
-    #[allow(clippy::too_many_arguments)]
     fn pardiso(
         &mut self,
-        a: &[f64],
-        ia: &[i32],
-        ja: &[i32],
+        matrix: &CsrMatrix,
         b: &mut [f64],
         x: &mut [f64],
-        n: i32,
+        nrhs: i32,
+    ) -> Result<(), PardisoError>;
+
+    // as pardiso, but for the complex matrix types
+    fn pardiso_complex(
+        &mut self,
+        matrix: &CsrMatrix<Complex64>,
+        b: &mut [Complex64],
+        x: &mut [Complex64],
+        nrhs: i32,
+    ) -> Result<(), PardisoError>;
+
+    // as pardiso, but with a single precision factorization.  The
+    // precision is fixed when the matrix is analyzed
+    fn pardiso_f32(
+        &mut self,
+        matrix: &CsrMatrix<f32>,
+        b: &mut [f32],
+        x: &mut [f32],
         nrhs: i32,
     ) -> Result<(), PardisoError>;
 
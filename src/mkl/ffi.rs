@@ -1,11 +1,12 @@
 #![allow(non_camel_case_types)]
 
 use libloading::Symbol;
-use std::ffi::{c_int, c_void};
+use std::ffi::{c_char, c_int, c_void};
 
 #[derive(Debug)]
 pub(crate) struct MKLPardisoPointers<'a> {
     pub pardiso: Symbol<'a, PARDISO>,
+    pub pardiso_64: Option<Symbol<'a, PARDISO_64>>,
     pub pardisoinit: Symbol<'a, PARDISOINIT>,
     pub mkl_set_num_threads: Symbol<'a, MKL_SET_NUM_THREADS>,
     pub mkl_set_num_threads_local: Symbol<'a, MKL_SET_NUM_THREADS_LOCAL>,
@@ -14,11 +15,18 @@ pub(crate) struct MKLPardisoPointers<'a> {
     pub mkl_domain_get_max_threads: Symbol<'a, MKL_DOMAIN_GET_MAX_THREADS>,
     #[allow(dead_code)]
     pub mkl_set_dynamic: Symbol<'a, MKL_SET_DYNAMIC>,
+    pub pardiso_setenv: Option<Symbol<'a, PARDISO_SETENV>>,
+    pub pardiso_getenv: Option<Symbol<'a, PARDISO_GETENV>>,
+    pub pardiso_handle_store: Option<Symbol<'a, PARDISO_HANDLE_STORE>>,
+    pub pardiso_handle_restore: Option<Symbol<'a, PARDISO_HANDLE_RESTORE>>,
+    pub pardiso_handle_delete: Option<Symbol<'a, PARDISO_HANDLE_DELETE>>,
+    pub pardiso_getdiag: Option<Symbol<'a, PARDISO_GETDIAG>>,
 }
 
 // function signatures differ between MKL and Panua, so
 // they are defined separately.  Note in particular that
-// the MKL version does not have dparm
+// the MKL version does not have dparm.  The matrix values,
+// rhs and solution are untyped since they may be real or complex
 
 pub(crate) type PARDISO = extern "C" fn(
     pt: *mut c_void,
@@ -27,20 +35,54 @@ pub(crate) type PARDISO = extern "C" fn(
     mtype: *const i32,
     phase: *const i32,
     n: *const i32,
-    a: *const f64,
+    a: *const c_void,
     ia: *const i32,
     ja: *const i32,
     perm: *mut i32,
     nrhs: *const i32,
     iparm: *mut i32,
     msglvl: *const i32,
-    b: *mut f64,
-    x: *mut f64,
+    b: *mut c_void,
+    x: *mut c_void,
     error: *mut i32,
 );
 
+// 64-bit integer (ILP64) version.  All integer arguments are
+// 64-bit, including iparm and perm
+pub(crate) type PARDISO_64 = extern "C" fn(
+    pt: *mut c_void,
+    maxfct: *const i64,
+    mnum: *const i64,
+    mtype: *const i64,
+    phase: *const i64,
+    n: *const i64,
+    a: *const c_void,
+    ia: *const i64,
+    ja: *const i64,
+    perm: *mut i64,
+    nrhs: *const i64,
+    iparm: *mut i64,
+    msglvl: *const i64,
+    b: *mut c_void,
+    x: *mut c_void,
+    error: *mut i64,
+);
+
 pub(crate) type PARDISOINIT = extern "C" fn(pt: *mut c_void, mtype: *const i32, iparm: *mut i32);
 
+// as defined in mkl_pardiso.h: enum PARDISO_ENV_PARAM
+pub(crate) const PARDISO_OOC_FILE_NAME: c_int = 1;
+
+// per-handle settings given as strings, currently only the prefix of
+// the out-of-core files.  The parameter is passed by pointer, and getenv
+// requires a buffer of at least 1024 characters.  Both return nonzero on
+// failure
+pub(crate) type PARDISO_SETENV =
+    extern "C" fn(pt: *mut c_void, param: *const c_int, value: *const c_char) -> i32;
+
+pub(crate) type PARDISO_GETENV =
+    extern "C" fn(pt: *const c_void, param: *const c_int, value: *mut c_char) -> i32;
+
 // MKL C documentation says that the thread get/set functions take c_int, which
 // appears to be wrong since they call into fortran functions that expect c_int*
 
@@ -56,3 +98,23 @@ pub(crate) type MKL_GET_MAX_THREADS = extern "C" fn() -> c_int;
 pub(crate) type MKL_DOMAIN_GET_MAX_THREADS = extern "C" fn(domain: *const c_int) -> c_int;
 
 pub(crate) type MKL_SET_DYNAMIC = extern "C" fn(dynamic: *const c_int);
+
+// serialization of the internal handle to files in a directory.  The
+// directory name is used as a prefix of the file names
+pub(crate) type PARDISO_HANDLE_STORE =
+    extern "C" fn(pt: *mut c_void, dirname: *const c_char, error: *mut i32);
+
+pub(crate) type PARDISO_HANDLE_RESTORE =
+    extern "C" fn(pt: *mut c_void, dirname: *const c_char, error: *mut i32);
+
+pub(crate) type PARDISO_HANDLE_DELETE = extern "C" fn(dirname: *const c_char, error: *mut i32);
+
+// diagonals of the factor (df) and of the original matrix (da) for
+// matrix number mnum.  Requires iparm[55] = 1 during factorization
+pub(crate) type PARDISO_GETDIAG = extern "C" fn(
+    pt: *const c_void,
+    df: *mut c_void,
+    da: *mut c_void,
+    mnum: *const i32,
+    error: *mut i32,
+);
@@ -0,0 +1,106 @@
+use crate::{IndexBase, Inertia, MatrixType, PardisoData, PardisoError, Precision};
+use std::fmt::Write as _;
+use std::path::Path;
+
+// File holding the PardisoData parameters next to the files written
+// by pardiso_handle_store.  One line per entry, as "key values..."
+pub(crate) const STATE_FILE: &str = "pardiso_wrapper_state.txt";
+
+pub(crate) fn write_state(data: &PardisoData, dir: &Path) -> Result<(), PardisoError> {
+    let join = |v: &[i32]| {
+        v.iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let mut s = String::new();
+    let _ = writeln!(s, "mtype {}", data.mtype as i32);
+    let _ = writeln!(s, "n {}", data.n);
+    let _ = writeln!(s, "maxfct {}", data.maxfct);
+    let _ = writeln!(s, "mnum {}", data.mnum);
+    let _ = writeln!(s, "iparm {}", join(&data.iparm));
+    let _ = writeln!(s, "perm {}", join(&data.perm));
+    let _ = writeln!(s, "perm_base {}", data.perm_base as i32);
+    let precision = match data.precision {
+        None => "none",
+        Some(Precision::Double) => "double",
+        Some(Precision::Single) => "single",
+    };
+    let _ = writeln!(s, "precision {precision}");
+    for (mnum, inertia) in &data.factored {
+        let Inertia {
+            positive,
+            negative,
+            zero,
+            perturbed_pivots,
+        } = inertia;
+        let _ = writeln!(
+            s,
+            "factored {mnum} {positive} {negative} {zero} {perturbed_pivots}"
+        );
+    }
+
+    std::fs::write(dir.join(STATE_FILE), s)?;
+    Ok(())
+}
+
+pub(crate) fn read_state(dir: &Path) -> Result<PardisoData, PardisoError> {
+    let invalid = |line: &str| PardisoError::InvalidSavedState(line.to_string());
+    let text = std::fs::read_to_string(dir.join(STATE_FILE))?;
+
+    let mut data = PardisoData::default();
+    for line in text.lines() {
+        let (key, values) = line.split_once(' ').unwrap_or((line, ""));
+        if key == "precision" {
+            data.precision = match values {
+                "none" => None,
+                "double" => Some(Precision::Double),
+                "single" => Some(Precision::Single),
+                _ => return Err(invalid(line)),
+            };
+            continue;
+        }
+        let ints = values
+            .split_whitespace()
+            .map(|v| v.parse::<i32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid(line))?;
+        let single = || match ints[..] {
+            [v] => Ok(v),
+            _ => Err(invalid(line)),
+        };
+
+        match key {
+            "mtype" => {
+                data.mtype = MatrixType::try_from(single()?).map_err(|_| invalid(line))?;
+            }
+            "n" => data.n = usize::try_from(single()?).map_err(|_| invalid(line))?,
+            "maxfct" => data.maxfct = single()?,
+            "mnum" => data.mnum = single()?,
+            "iparm" => data.iparm = ints.try_into().map_err(|_| invalid(line))?,
+            "perm" => data.perm = ints,
+            "perm_base" => {
+                data.perm_base = match single()? {
+                    0 => IndexBase::Zero,
+                    1 => IndexBase::One,
+                    _ => return Err(invalid(line)),
+                }
+            }
+            "factored" => {
+                let [mnum, positive, negative, zero, perturbed_pivots] = ints[..] else {
+                    return Err(invalid(line));
+                };
+                let inertia = Inertia {
+                    positive,
+                    negative,
+                    zero,
+                    perturbed_pivots,
+                };
+                data.factored.insert(mnum, inertia);
+            }
+            _ => return Err(invalid(line)),
+        }
+    }
+    Ok(data)
+}
@@ -1,12 +1,214 @@
+use super::ffi::PARDISO_OOC_FILE_NAME;
+use super::handle::{read_state, write_state, STATE_FILE};
 use super::loader::*;
-use crate::{MKLPardisoError, PardisoData, PardisoError, PardisoInterface};
-use std::ffi::c_void;
+use super::ooc::OutOfCoreConfig;
+#[cfg(feature = "mkl-pivot-hook")]
+use super::pivot::{self, PivotHandler};
+use crate::dense::{check_indices, scatter, schur_block, solve_sparse_dense};
+use crate::{
+    Complex64, CsrMatrix, DenseMatrix, IParm, IndexBase, MKLPardisoError, Ordering, PardisoData,
+    PardisoError, PardisoIndex, PardisoInterface, PardisoScalar, Phase, Precision, Sign,
+    SolverStats, Transpose,
+};
+use libloading::Symbol;
+use std::ffi::{c_char, c_void, CStr, CString};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+#[cfg(feature = "mkl-pivot-hook")]
+use std::sync::Arc;
 
 // as defined in mkl_types.h: #define MKL_DOMAIN_PARDISO  4
 pub(crate) const MKL_DOMAIN_PARDISO: i32 = 4;
 
+static SOLVER_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// Entry points that are missing from some MKL versions, see MKL_SYMBOLS
+fn optional<'a, T>(
+    symbol: &'a Option<Symbol<'static, T>>,
+    option: &str,
+) -> Result<&'a Symbol<'static, T>, PardisoError> {
+    symbol
+        .as_ref()
+        .ok_or_else(|| PardisoError::UnsupportedOption {
+            backend: "mkl",
+            option: option.to_string(),
+        })
+}
+
 pub struct MKLPardisoSolver {
     _data: PardisoData,
+    // identifies the solver as owner of the pivot handler
+    _id: usize,
+    // whether the handle was created through pardiso_64
+    _ilp64: Option<bool>,
+    // full width iparm and perm of the last pardiso_64 call, of which
+    // PardisoData only holds saturated 32-bit copies
+    _iparm64: [i64; 64],
+    _perm64: Vec<i64>,
+    // mask used to analyze the matrix for sparse solves, see
+    // set_sparse_mask
+    _sparse_mask: Option<Vec<i32>>,
+    // out-of-core configuration and the prefix of its scratch files
+    _ooc: Option<(OutOfCoreConfig, PathBuf)>,
+    // keep the diagonals for factor_diagonal and original_diagonal
+    _keep_diagonal: bool,
+}
+
+impl MKLPardisoSolver {
+    // Common implementation for all scalar and index types.  The
+    // index type selects between pardiso and pardiso_64.  MKL does
+    // not allow mixing the two on the same handle, so the width is
+    // fixed by the first call and reset when the handle is released
+    pub fn pardiso_generic<T: PardisoScalar, I: PardisoIndex>(
+        &mut self,
+        matrix: &CsrMatrix<T, I>,
+        b: &mut [T],
+        x: &mut [T],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        let ptrs = mkl_ptrs()?;
+
+        let phase = self.data().phase;
+        let is_release = matches!(phase, Phase::ReleaseAll | Phase::ReleaseLUandMNUM);
+
+        let ilp64 = match self._ilp64 {
+            Some(ilp64) if is_release => ilp64,
+            Some(ilp64) if ilp64 != I::IS_64 => return Err(PardisoError::IndexWidthMismatch),
+            _ => I::IS_64,
+        };
+        let pardiso_64 = match ilp64 {
+            true => Some(optional(
+                &ptrs.pardiso_64,
+                "64-bit indices without pardiso_64",
+            )?),
+            false => None,
+        };
+
+        self.data().check_scalar::<T>()?;
+        self.data().check_precision(T::PRECISION)?;
+        self.data().check_buffers(matrix.n(), nrhs, b, x)?;
+        self.data_mut().prepare_perm(matrix.n(), matrix.base())?;
+
+        // single precision data requires a single precision factorization,
+        // and the index base is taken from the matrix.  The diagonals are
+        // only kept on request, which also sends small pivots through
+        // mkl_pardiso_pivot
+        if !is_release {
+            #[cfg(feature = "mkl-pivot-hook")]
+            let keep_diagonal = self._keep_diagonal || self.has_pivot_handler();
+            #[cfg(not(feature = "mkl-pivot-hook"))]
+            let keep_diagonal = self._keep_diagonal;
+            self.ensure_iparm_initialized()?;
+            let iparm = &mut self.data_mut().iparm;
+            iparm[27] = (T::PRECISION == Precision::Single) as i32;
+            iparm[34] = (matrix.base() == IndexBase::Zero) as i32;
+            if keep_diagonal {
+                iparm[55] = 1;
+            }
+        }
+
+        let pt = self.data_mut().pt.as_mut_ptr() as *mut c_void;
+        let maxfct = self.data().maxfct;
+        let mnum = self.data().mnum;
+        let mtype = self.get_matrix_type() as i32;
+        let phase = phase as i32;
+        let n = matrix.n();
+        let a = matrix.values().as_ptr() as *const c_void;
+        let ia = matrix.row_ptrs().as_ptr();
+        let ja = matrix.col_indices().as_ptr();
+        let b = b.as_mut_ptr() as *mut c_void;
+        let x = x.as_mut_ptr() as *mut c_void;
+        let msglvl = self.data().msglvl as i32;
+
+        let error = if let Some(pardiso_64) = pardiso_64 {
+            // integer arguments are widened for the call.  iparm and
+            // perm are kept at full width, see get_iparm64, and copied
+            // back saturated to the 32-bit copies held in PardisoData
+            let mut iparm = self.data().iparm.map(i64::from);
+            let mut perm: Vec<i64> = self.data().perm.iter().map(|&p| p as i64).collect();
+            let mut error = 0_i64;
+
+            pardiso_64(
+                pt,
+                &(maxfct as i64),
+                &(mnum as i64),
+                &(mtype as i64),
+                &(phase as i64),
+                &(n as i64),
+                a,
+                ia as *const i64,
+                ja as *const i64,
+                perm.as_mut_ptr(),
+                &(nrhs as i64),
+                iparm.as_mut_ptr(),
+                &(msglvl as i64),
+                b,
+                x,
+                &mut error,
+            );
+
+            let saturate = |v: i64| v.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+            let data = &mut self._data;
+            for (dst, &src) in data.iparm.iter_mut().zip(&iparm) {
+                *dst = saturate(src);
+            }
+            for (dst, &src) in data.perm.iter_mut().zip(&perm) {
+                *dst = saturate(src);
+            }
+            self._iparm64 = iparm;
+            self._perm64 = perm;
+            saturate(error)
+        } else {
+            let perm = self.data_mut().perm.as_mut_ptr();
+            let iparm = self.data_mut().iparm.as_mut_ptr();
+            let mut error = 0;
+
+            (ptrs.pardiso)(
+                pt,
+                &maxfct,
+                &mnum,
+                &mtype,
+                &phase,
+                &(n as i32),
+                a,
+                ia as *const i32,
+                ja as *const i32,
+                perm,
+                &nrhs,
+                iparm,
+                &msglvl,
+                b,
+                x,
+                &mut error,
+            );
+            error
+        };
+
+        if error != 0 {
+            let error = MKLPardisoError::from(error);
+            return Err(PardisoError::from(error));
+        }
+
+        self._ilp64 = match self.data().phase {
+            Phase::ReleaseAll => None,
+            _ => Some(ilp64),
+        };
+        // an analysis with iparm[30] = 1 records the mask for solve_sparse
+        // and turns the partial solve off again for other solves
+        let analyzed = matches!(
+            self.data().phase,
+            Phase::Analysis | Phase::AnalysisNumFact | Phase::AnalysisNumFactSolveRefine
+        );
+        if analyzed {
+            let data = &mut self._data;
+            self._sparse_mask = (data.iparm[30] != 0).then(|| data.perm.clone());
+            data.iparm[30] = 0;
+        } else if self.data().phase == Phase::ReleaseAll {
+            self._sparse_mask = None;
+        }
+        self.data_mut().record_call(T::PRECISION, matrix.n());
+        Ok(())
+    }
 }
 
 impl PardisoInterface for MKLPardisoSolver {
@@ -17,12 +219,37 @@ impl PardisoInterface for MKLPardisoSolver {
         &mut self._data
     }
 
+    // Entries set by the wrapper are only read when iparm[0] = 1.  With
+    // iparm[0] = 0 MKL would replace iparm with its defaults, so those
+    // defaults are filled in first
+    fn ensure_iparm_initialized(&mut self) -> Result<(), PardisoError> {
+        if self.data().iparm[0] == 0 {
+            let ptrs = mkl_ptrs()?;
+            let mut pt = [0_isize; 64];
+            let mut iparm = [0; 64];
+            let mtype = self.get_matrix_type() as i32;
+            (ptrs.pardisoinit)(pt.as_mut_ptr() as *mut c_void, &mtype, iparm.as_mut_ptr());
+            self.data_mut().iparm = iparm;
+        }
+        self.data_mut().iparm[0] = 1;
+        Ok(())
+    }
+
     fn new() -> Result<Self, PardisoError> {
         if !MKLPardisoSolver::is_loaded() {
             return Err(MKLPardisoError::LibraryLoadFailure)?;
         }
         let data = PardisoData::default();
-        Ok(Self { _data: data })
+        Ok(Self {
+            _data: data,
+            _id: SOLVER_COUNTER.fetch_add(1, AtomicOrdering::Relaxed),
+            _ilp64: None,
+            _iparm64: [0; 64],
+            _perm64: vec![],
+            _sparse_mask: None,
+            _ooc: None,
+            _keep_diagonal: false,
+        })
     }
 
     fn pardisoinit(&mut self) -> Result<(), PardisoError> {
@@ -39,45 +266,183 @@ impl PardisoInterface for MKLPardisoSolver {
 
     fn pardiso(
         &mut self,
-        a: &[f64],
-        ia: &[i32],
-        ja: &[i32],
+        matrix: &CsrMatrix,
         b: &mut [f64],
         x: &mut [f64],
-        n: i32,
         nrhs: i32,
     ) -> Result<(), PardisoError> {
-        let ptrs = mkl_ptrs()?;
+        self.pardiso_generic(matrix, b, x, nrhs)
+    }
 
-        let mut error = 0;
-        let pt = self.data_mut().pt.as_mut_ptr() as *mut c_void;
-        let maxfct = self.data().maxfct;
-        let mnum = self.data().mnum;
-        let mtype = self.get_matrix_type() as i32;
-        let phase = self.data().phase as i32;
-        let a = a.as_ptr();
-        let ia = ia.as_ptr();
-        let ja = ja.as_ptr();
-        let b = b.as_mut_ptr();
-        let x = x.as_mut_ptr();
-        let perm = self.data_mut().perm.as_mut_ptr();
-        let iparm = self.data_mut().iparm.as_mut_ptr();
-        let msglvl = self.data().msglvl as i32;
+    fn pardiso_complex(
+        &mut self,
+        matrix: &CsrMatrix<Complex64>,
+        b: &mut [Complex64],
+        x: &mut [Complex64],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        self.pardiso_generic(matrix, b, x, nrhs)
+    }
 
-        (ptrs.pardiso)(
-            pt, &maxfct, &mnum, &mtype, &phase, &n, a, ia, ja, perm, &nrhs, iparm, &msglvl, b, x,
-            &mut error,
-        );
+    fn pardiso_f32(
+        &mut self,
+        matrix: &CsrMatrix<f32>,
+        b: &mut [f32],
+        x: &mut [f32],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        self.pardiso_generic(matrix, b, x, nrhs)
+    }
 
-        if error != 0 {
-            let error = MKLPardisoError::from(error);
-            return Err(PardisoError::from(error));
+    fn name(&self) -> &'static str {
+        "mkl"
+    }
+
+    fn set_ordering(&mut self, ordering: Ordering) -> Result<(), PardisoError> {
+        self.ensure_iparm_initialized()?;
+        let iparm = &mut self.data_mut().iparm;
+        match ordering {
+            Ordering::User => iparm[4] = 1,
+            _ => {
+                iparm[1] = match ordering {
+                    Ordering::Amd => 0,
+                    Ordering::ParallelMetis => 3,
+                    _ => 2,
+                };
+                // stop using any user permutation
+                if iparm[4] == 1 {
+                    iparm[4] = 0;
+                }
+            }
         }
         Ok(())
     }
 
-    fn name(&self) -> &'static str {
-        "mkl"
+    fn get_ordering(&self) -> Ordering {
+        let iparm = &self.data().iparm;
+        match (iparm[4], iparm[1]) {
+            (1, _) => Ordering::User,
+            (_, 0) => Ordering::Amd,
+            (_, 3) => Ordering::ParallelMetis,
+            _ => Ordering::Metis,
+        }
+    }
+
+    fn set_iparm_options(&mut self, options: &IParm) -> Result<(), PardisoError> {
+        self.ensure_iparm_initialized()?;
+        self.set_ordering(options.ordering)?;
+        let transpose = self.transpose_code(options.transpose);
+        let iparm = &mut self.data_mut().iparm;
+        options.write_common(iparm);
+        iparm[11] = transpose;
+        Ok(())
+    }
+
+    fn get_iparm_options(&self) -> IParm {
+        let iparm = &self.data().iparm;
+        let transpose = match iparm[11] {
+            1 => Transpose::ConjugateTranspose,
+            2 => Transpose::Transpose,
+            _ => Transpose::None,
+        };
+        IParm::read_common(iparm, self.get_ordering(), transpose)
+    }
+
+    fn transpose_code(&self, transpose: Transpose) -> i32 {
+        // NB: MKL uses 1 for the conjugate transpose
+        match transpose {
+            Transpose::None => 0,
+            Transpose::ConjugateTranspose => 1,
+            Transpose::Transpose => 2,
+        }
+    }
+
+    fn get_stats(&self) -> SolverStats {
+        // MKL only reports the factor size and operation count when
+        // iparm[17] and iparm[18] are negative on entry.  Otherwise
+        // they are left unchanged, so anything nonpositive is treated
+        // as not reported, as are memory sizes before the phase that
+        // computes them
+        let iparm = &self.data().iparm;
+        let memory = |v: i32| (v > 0).then_some(v);
+        let reported = |i: usize| {
+            let v = match self._ilp64 {
+                Some(true) => self._iparm64[i],
+                _ => iparm[i].into(),
+            };
+            (v > 0).then_some(v)
+        };
+        SolverStats {
+            phase: self.data().phase,
+            peak_analysis_memory_kb: memory(iparm[14]),
+            permanent_memory_kb: memory(iparm[15]),
+            factor_memory_kb: memory(iparm[16]),
+            factor_nnz: reported(17),
+            factor_mflop: reported(18),
+            perturbed_pivots: iparm[13],
+            refinement_steps: iparm[6],
+        }
+    }
+
+    // MKL computes only the required parts of the solve (iparm[30] = 1)
+    // with perm marking both the nonzeros of b and the wanted components
+    // of x.  The mask must also be given at analysis, see set_sparse_mask,
+    // so patterns outside of it are solved densely
+    fn solve_sparse(
+        &mut self,
+        matrix: &CsrMatrix,
+        rhs_indices: &[usize],
+        rhs_values: &[f64],
+        wanted_indices: &[usize],
+    ) -> Result<Vec<f64>, PardisoError> {
+        let n = matrix.n();
+        let covered = |mask: &Vec<i32>| {
+            mask.len() == n
+                && rhs_indices
+                    .iter()
+                    .chain(wanted_indices)
+                    .all(|&i| mask.get(i) == Some(&1))
+        };
+        let Some(mask) = self._sparse_mask.clone().filter(covered) else {
+            return solve_sparse_dense(self, matrix, rhs_indices, rhs_values, wanted_indices);
+        };
+        self.data().check_factored()?;
+        let mut b = scatter(n, rhs_indices, rhs_values)?;
+        check_indices(n, wanted_indices)?;
+
+        // put aside any user ordering and refinement, which cannot be
+        // combined with a partial solve
+        self.ensure_iparm_initialized()?;
+        let data = self.data_mut();
+        let perm = std::mem::replace(&mut data.perm, mask);
+        let (perm_mode, refinement) = (data.iparm[4], data.iparm[7]);
+        data.iparm[4] = 0;
+        data.iparm[7] = 0;
+        data.iparm[30] = 1;
+
+        let mut x = vec![0.0; n];
+        self.set_phase(Phase::SolveIterativeRefine);
+        let result = self.pardiso(matrix, &mut b, &mut x, 1);
+
+        let data = self.data_mut();
+        data.perm = perm;
+        data.iparm[4] = perm_mode;
+        data.iparm[7] = refinement;
+        data.iparm[30] = 0;
+        result?;
+
+        let x = if self.data().iparm[5] == 1 { b } else { x };
+        Ok(wanted_indices.iter().map(|&i| x[i]).collect())
+    }
+
+    fn log_determinant(&self) -> Result<(f64, Sign), PardisoError> {
+        // iparm[32] is reserved in MKL, which does not report the
+        // determinant of the factorization
+        self.data().check_factored()?;
+        Err(PardisoError::UnsupportedOption {
+            backend: "mkl",
+            option: "Determinant computation".to_string(),
+        })
     }
 
     fn is_licensed() -> bool {
@@ -95,6 +460,21 @@ impl PardisoInterface for MKLPardisoSolver {
 
 // additional MKL specific functions
 impl MKLPardisoSolver {
+    // Full width iparm and perm after a call through pardiso_64, e.g.
+    // the factor size of matrices with more than 2^31 nonzeros.  For
+    // other handles these are the 32-bit values
+    pub fn get_iparm64(&self, i: usize) -> i64 {
+        match self._ilp64 {
+            Some(true) => self._iparm64[i],
+            _ => self.data().iparm[i].into(),
+        }
+    }
+    pub fn get_perm64(&self) -> Vec<i64> {
+        match self._ilp64 {
+            Some(true) => self._perm64.clone(),
+            _ => self.data().perm.iter().map(|&p| p.into()).collect(),
+        }
+    }
     pub fn set_num_threads(&mut self, num_threads: i32) -> Result<i32, PardisoError> {
         Ok(MKLPardisoSolver::mkl_set_num_threads_local(num_threads)?)
     }
@@ -132,8 +512,374 @@ impl MKLPardisoSolver {
     }
 }
 
+impl MKLPardisoSolver {
+    /// Computes the dense Schur complement of the block of rows and
+    /// columns given by `block_indices` (zero-based, in any order).
+    ///
+    /// Row and column `i` of the result correspond to the `i`-th smallest
+    /// block index.  This runs `Phase::AnalysisNumFact`, after which the
+    /// handle holds a partial factorization that cannot be used to solve
+    /// with the full matrix.  Methods that need the factorization return
+    /// `NotFactorized` until the matrix has been analyzed and factorized
+    /// again.
+    pub fn schur_complement(
+        &mut self,
+        matrix: &CsrMatrix,
+        block_indices: &[usize],
+    ) -> Result<DenseMatrix, PardisoError> {
+        let block = schur_block(matrix.n(), block_indices)?;
+        let k = block.len();
+
+        // perm is used as a mask marking the block, so any user or
+        // returned ordering is put aside for the call
+        self.ensure_iparm_initialized()?;
+        let data = self.data_mut();
+        let perm = std::mem::replace(&mut data.perm, vec![0; matrix.n()]);
+        let perm_mode = data.iparm[4];
+        block.iter().for_each(|&i| data.perm[i] = 1);
+        data.iparm[4] = 0;
+        data.iparm[35] = 1;
+        data.phase = Phase::AnalysisNumFact;
+
+        let mut s = vec![0.0; k * k];
+        let result = self.pardiso(matrix, &mut [], &mut s, 1);
+
+        let data = self.data_mut();
+        data.perm = perm;
+        data.iparm[4] = perm_mode;
+        data.iparm[35] = 0;
+        data.factored.remove(&data.mnum);
+        result?;
+
+        Ok(DenseMatrix::from_row_major(k, k, s))
+    }
+}
+
+impl MKLPardisoSolver {
+    /// Prepares the next analysis for sparse solves with
+    /// [`solve_sparse`](PardisoInterface::solve_sparse).
+    ///
+    /// `indices` marks every entry of the right-hand sides that may be
+    /// nonzero and every component of the solution that may be wanted.
+    /// MKL needs this mask when the matrix is analyzed, so call this
+    /// before the analysis; it replaces any user ordering.  Sparse solves
+    /// whose indices are all marked only compute the required parts of
+    /// the solve, others fall back to a dense solve.
+    pub fn set_sparse_mask(&mut self, n: usize, indices: &[usize]) -> Result<(), PardisoError> {
+        check_indices(n, indices)?;
+        self.ensure_iparm_initialized()?;
+        let data = self.data_mut();
+        data.perm = vec![0; n];
+        indices.iter().for_each(|&i| data.perm[i] = 1);
+        data.iparm[4] = 0;
+        data.iparm[30] = 1;
+        Ok(())
+    }
+}
+
+impl MKLPardisoSolver {
+    /// Saves the internal solver handle and parameters to a directory.
+    ///
+    /// A later process can resume solving with the same analysis and
+    /// factorizations with [`restore_handle`](Self::restore_handle),
+    /// without repeating them.  The directory is created if needed.
+    pub fn save_handle(&self, dir: impl AsRef<Path>) -> Result<(), PardisoError> {
+        let ptrs = mkl_ptrs()?;
+        let dir = dir.as_ref();
+        if self._ilp64 == Some(true) {
+            return Err(PardisoError::UnsupportedOption {
+                backend: "mkl",
+                option: "Saving a handle with 64-bit indices".to_string(),
+            });
+        }
+        std::fs::create_dir_all(dir)?;
+        let dirname = handle_dirname(dir)?;
+
+        // pardiso_handle_store only reads the handle
+        let mut error = 0;
+        let pt = self.data().pt.as_ptr() as *mut c_void;
+        let store = optional(
+            &ptrs.pardiso_handle_store,
+            "Saving a handle without pardiso_handle_store",
+        )?;
+        store(pt, dirname.as_ptr(), &mut error);
+        if error != 0 {
+            return Err(MKLPardisoError::from(error))?;
+        }
+        write_state(self.data(), dir)
+    }
+
+    /// Creates a solver from a handle saved with
+    /// [`save_handle`](Self::save_handle).
+    pub fn restore_handle(dir: impl AsRef<Path>) -> Result<Self, PardisoError> {
+        let restore = optional(
+            &mkl_ptrs()?.pardiso_handle_restore,
+            "Restoring a handle without pardiso_handle_restore",
+        )?;
+        let dir = dir.as_ref();
+        let data = read_state(dir)?;
+        let dirname = handle_dirname(dir)?;
+
+        let mut ps = Self::new()?;
+        let mut error = 0;
+        let pt = ps.data_mut().pt.as_mut_ptr() as *mut c_void;
+        restore(pt, dirname.as_ptr(), &mut error);
+        if error != 0 {
+            return Err(MKLPardisoError::from(error))?;
+        }
+
+        let pt = ps._data.pt;
+        ps._data = PardisoData { pt, ..data };
+        ps._ilp64 = ps._data.precision.map(|_| false);
+        Ok(ps)
+    }
+
+    /// Removes the files written by [`save_handle`](Self::save_handle).
+    pub fn delete_handle(dir: impl AsRef<Path>) -> Result<(), PardisoError> {
+        let delete = optional(
+            &mkl_ptrs()?.pardiso_handle_delete,
+            "Deleting a handle without pardiso_handle_delete",
+        )?;
+        let dir = dir.as_ref();
+        let dirname = handle_dirname(dir)?;
+
+        let mut error = 0;
+        delete(dirname.as_ptr(), &mut error);
+        if error != 0 {
+            return Err(MKLPardisoError::from(error))?;
+        }
+        match std::fs::remove_file(dir.join(STATE_FILE)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e)?,
+            _ => Ok(()),
+        }
+    }
+}
+
+impl MKLPardisoSolver {
+    // Keep the diagonals of the matrix and its factors (iparm[55] = 1)
+    // in later factorizations, as needed by factor_diagonal and
+    // original_diagonal.  This is also done while a pivot handler is
+    // installed (feature mkl-pivot-hook)
+    pub fn set_keep_diagonal(&mut self, keep: bool) {
+        self._keep_diagonal = keep;
+    }
+    pub fn get_keep_diagonal(&self) -> bool {
+        self._keep_diagonal
+    }
+
+    // Diagonal of the factorization of the current matrix number, e.g.
+    // of D in A = L D L^T.  The scalar type must match the matrix type
+    // and the precision of the factorization
+    pub fn factor_diagonal<T: PardisoScalar>(&self) -> Result<Vec<T>, PardisoError> {
+        Ok(self.getdiag()?.0)
+    }
+
+    // diagonal of the matrix that was factorized for the current mnum
+    pub fn original_diagonal<T: PardisoScalar>(&self) -> Result<Vec<T>, PardisoError> {
+        Ok(self.getdiag()?.1)
+    }
+
+    fn getdiag<T: PardisoScalar>(&self) -> Result<(Vec<T>, Vec<T>), PardisoError> {
+        let getdiag = optional(
+            &mkl_ptrs()?.pardiso_getdiag,
+            "Reading the factor diagonal without pardiso_getdiag",
+        )?;
+        let data = self.data();
+        data.check_factored()?;
+        if data.iparm[55] != 1 {
+            return Err(PardisoError::UnsupportedOption {
+                backend: "mkl",
+                option: "Reading diagonals without set_keep_diagonal".to_string(),
+            });
+        }
+        data.check_scalar::<T>()?;
+        data.check_precision(T::PRECISION)?;
+
+        let mut df = vec![T::default(); data.n];
+        let mut da = vec![T::default(); data.n];
+        let mut error = 0;
+        let pt = data.pt.as_ptr() as *const c_void;
+
+        getdiag(
+            pt,
+            df.as_mut_ptr() as *mut c_void,
+            da.as_mut_ptr() as *mut c_void,
+            &data.mnum,
+            &mut error,
+        );
+
+        if error != 0 {
+            return Err(MKLPardisoError::from(error))?;
+        }
+        Ok((df, da))
+    }
+}
+
+// MKL prepends the directory name to its file names as is, so it
+// must end with a separator.  Paths that cannot be passed as a C
+// string (non-UTF-8 or containing a nul byte) are not supported
+fn handle_dirname(dir: &Path) -> Result<CString, PardisoError> {
+    let unsupported = || PardisoError::UnsupportedOption {
+        backend: "mkl",
+        option: format!("Handle directory {}", dir.display()),
+    };
+    let mut dirname = dir.to_str().ok_or_else(unsupported)?.to_string();
+    if !dirname.ends_with(std::path::MAIN_SEPARATOR) {
+        dirname.push(std::path::MAIN_SEPARATOR);
+    }
+    CString::new(dirname).map_err(|_| unsupported())
+}
+
+// distinguishes the scratch files of solvers in the same process
+static OOC_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+impl MKLPardisoSolver {
+    /// Configures out-of-core (OOC) storage of the factors.
+    ///
+    /// This must be called after [`pardisoinit`](PardisoInterface::pardisoinit),
+    /// which clears the handle and with it the file prefix, and before the
+    /// numerical factorization.  The scratch files are given a prefix
+    /// unique to this solver and are removed when it is dropped.
+    ///
+    /// The in-core memory limit of `OutOfCoreMode::Auto` is not configured
+    /// by this crate.  MKL reads it only from the process environment
+    /// (`MKL_PARDISO_OOC_MAX_CORE_SIZE`, in megabytes) or from a
+    /// `pardiso_ooc.cfg` file in the working directory, both of which are
+    /// shared by all solvers in the process, and changing the environment
+    /// is not thread safe.  Set the limit before creating any solvers.
+    pub fn set_out_of_core(&mut self, config: &OutOfCoreConfig) -> Result<(), PardisoError> {
+        let setenv = optional(
+            &mkl_ptrs()?.pardiso_setenv,
+            "Out-of-core storage without pardiso_setenv",
+        )?;
+
+        let directory = config
+            .directory
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("."));
+        if !directory.is_dir() {
+            return Err(MKLPardisoError::ErrorOpeningOOCFiles)?;
+        }
+        let prefix = directory.join(format!(
+            "pardiso_ooc_{}_{}_",
+            std::process::id(),
+            OOC_COUNTER.fetch_add(1, AtomicOrdering::Relaxed)
+        ));
+        let value = prefix
+            .to_str()
+            .and_then(|p| CString::new(p).ok())
+            .ok_or(MKLPardisoError::ErrorOpeningOOCFiles)?;
+
+        let pt = self.data_mut().pt.as_mut_ptr() as *mut c_void;
+        if setenv(pt, &PARDISO_OOC_FILE_NAME, value.as_ptr()) != 0 {
+            return Err(MKLPardisoError::ErrorOpeningOOCFiles)?;
+        }
+
+        self.ensure_iparm_initialized()?;
+        self.data_mut().iparm[59] = config.mode as i32;
+
+        // files written with an earlier prefix are no longer needed
+        if let Some((_, old)) = self._ooc.replace((config.clone(), prefix)) {
+            remove_ooc_files(&old);
+        }
+        Ok(())
+    }
+
+    pub fn get_out_of_core(&self) -> Option<&OutOfCoreConfig> {
+        self._ooc.as_ref().map(|(config, _)| config)
+    }
+
+    // prefix of the OOC scratch files as reported by MKL
+    pub fn get_out_of_core_prefix(&self) -> Result<String, PardisoError> {
+        let getenv = optional(
+            &mkl_ptrs()?.pardiso_getenv,
+            "Out-of-core storage without pardiso_getenv",
+        )?;
+        let mut value = vec![0 as c_char; 1024];
+        let pt = self.data().pt.as_ptr() as *const c_void;
+        if getenv(pt, &PARDISO_OOC_FILE_NAME, value.as_mut_ptr()) != 0 {
+            return Err(MKLPardisoError::ErrorOpeningOOCFiles)?;
+        }
+        let value = unsafe { CStr::from_ptr(value.as_ptr()) };
+        Ok(value.to_string_lossy().into_owned())
+    }
+}
+
+// removes any scratch files starting with the given prefix, which ends
+// in a separator so that it is not a prefix of another solver's
+pub(crate) fn remove_ooc_files(prefix: &Path) {
+    let (Some(directory), Some(name)) = (prefix.parent(), prefix.file_name()) else {
+        return;
+    };
+    let directory = if directory.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        directory
+    };
+    let Ok(entries) = std::fs::read_dir(directory) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(&*name.to_string_lossy())
+        {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(feature = "mkl-pivot-hook")]
+impl MKLPardisoSolver {
+    /// Installs `handler` as the rule for replacing small pivots.
+    ///
+    /// The hook is only called for factorizations with `iparm[55] = 1`,
+    /// which this solver sets while it owns the handler (see
+    /// [`set_keep_diagonal`](Self::set_keep_diagonal)).  MKL has a single
+    /// pivot hook, so the handler is process-global: it also applies to
+    /// other solvers that set `iparm[55] = 1` while installed.  To
+    /// avoid conflicting rules it is owned by this solver until
+    /// [`clear_pivot_handler`](Self::clear_pivot_handler) is called or the
+    /// solver is dropped, and installing one from another solver fails
+    /// with [`PardisoError::PivotHandlerInUse`].  The handler must not
+    /// panic; a panic aborts the process.  See [`PivotHandler`] for its
+    /// arguments.
+    ///
+    /// The handler is reached through the `mkl_pardiso_pivot` symbol
+    /// exported by this crate with the `mkl-pivot-hook` feature, which MKL
+    /// only picks up if the final executable exports it (e.g. when linked
+    /// with `-rdynamic`).
+    pub fn set_pivot_handler<F>(&mut self, handler: F) -> Result<(), PardisoError>
+    where
+        F: Fn(f64, f64, f64) -> Option<f64> + Send + Sync + 'static,
+    {
+        let handler: Arc<PivotHandler> = Arc::new(handler);
+        pivot::install(self._id, handler)
+    }
+
+    // removes the pivot handler if this solver installed it.  Later
+    // factorizations keep the diagonals only if set_keep_diagonal asks
+    // for it
+    pub fn clear_pivot_handler(&mut self) {
+        if self.has_pivot_handler() && !self._keep_diagonal {
+            self.data_mut().iparm[55] = 0;
+        }
+        pivot::clear(self._id);
+    }
+
+    pub fn has_pivot_handler(&self) -> bool {
+        pivot::owner() == Some(self._id)
+    }
+}
+
 impl Drop for MKLPardisoSolver {
     fn drop(&mut self) {
         self.release();
+        #[cfg(feature = "mkl-pivot-hook")]
+        self.clear_pivot_handler();
+        if let Some((_, prefix)) = &self._ooc {
+            remove_ooc_files(prefix);
+        }
     }
 }
@@ -116,9 +116,19 @@ lazy_static! {
         let mkl_get_max_threads: Symbol<MKL_GET_MAX_THREADS> = unsafe { lib.get::<MKL_GET_MAX_THREADS>(b"mkl_get_max_threads").ok()? };
         let mkl_domain_get_max_threads: Symbol<MKL_DOMAIN_GET_MAX_THREADS> = unsafe { lib.get::<MKL_DOMAIN_GET_MAX_THREADS>(b"mkl_domain_get_max_threads").ok()? };
         let mkl_set_dynamic: Symbol<MKL_SET_DYNAMIC> = unsafe { lib.get::<MKL_SET_DYNAMIC>(b"mkl_set_dynamic").ok()? };
+        // the remaining entry points are missing from some MKL versions,
+        // and are only required by the methods that use them
+        let pardiso_64: Option<Symbol<PARDISO_64>> = unsafe { lib.get::<PARDISO_64>(b"pardiso_64").ok() };
+        let pardiso_setenv: Option<Symbol<PARDISO_SETENV>> = unsafe { lib.get::<PARDISO_SETENV>(b"pardiso_setenv").ok() };
+        let pardiso_getenv: Option<Symbol<PARDISO_GETENV>> = unsafe { lib.get::<PARDISO_GETENV>(b"pardiso_getenv").ok() };
+        let pardiso_handle_store: Option<Symbol<PARDISO_HANDLE_STORE>> = unsafe { lib.get::<PARDISO_HANDLE_STORE>(b"pardiso_handle_store").ok() };
+        let pardiso_handle_restore: Option<Symbol<PARDISO_HANDLE_RESTORE>> = unsafe { lib.get::<PARDISO_HANDLE_RESTORE>(b"pardiso_handle_restore").ok() };
+        let pardiso_handle_delete: Option<Symbol<PARDISO_HANDLE_DELETE>> = unsafe { lib.get::<PARDISO_HANDLE_DELETE>(b"pardiso_handle_delete").ok() };
+        let pardiso_getdiag: Option<Symbol<PARDISO_GETDIAG>> = unsafe { lib.get::<PARDISO_GETDIAG>(b"pardiso_getdiag").ok() };
 
         Some(MKLPardisoPointers {
             pardiso,
+            pardiso_64,
             pardisoinit,
             mkl_set_num_threads,
             mkl_set_num_threads_local,
@@ -126,6 +136,12 @@ lazy_static! {
             mkl_get_max_threads,
             mkl_domain_get_max_threads,
             mkl_set_dynamic,
+            pardiso_setenv,
+            pardiso_getenv,
+            pardiso_handle_store,
+            pardiso_handle_restore,
+            pardiso_handle_delete,
+            pardiso_getdiag,
         })
     };
 }
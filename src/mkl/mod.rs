@@ -1,4 +1,11 @@
 pub(crate) mod ffi;
+pub(crate) mod handle;
 pub(crate) mod interface;
 pub(crate) mod loader;
+pub(crate) mod ooc;
+#[cfg(feature = "mkl-pivot-hook")]
+pub(crate) mod pivot;
 pub use interface::*;
+pub use ooc::*;
+#[cfg(feature = "mkl-pivot-hook")]
+pub use pivot::PivotHandler;
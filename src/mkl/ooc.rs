@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+/// When MKL stores the factors on disk instead of in memory (`iparm[59]`).
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutOfCoreMode {
+    // factors are kept in memory
+    #[default]
+    InCore = 0,
+    // in memory if they fit within MKL_PARDISO_OOC_MAX_CORE_SIZE, on
+    // disk otherwise
+    Auto = 1,
+    // factors are always stored on disk
+    Always = 2,
+}
+
+impl std::fmt::Display for OutOfCoreMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            OutOfCoreMode::InCore => "In-Core",
+            OutOfCoreMode::Auto => "Automatic Out-of-Core",
+            OutOfCoreMode::Always => "Out-of-Core",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Out-of-core (OOC) configuration for [`MKLPardisoSolver`](crate::MKLPardisoSolver).
+///
+/// The scratch files are written to `directory` (the working directory if
+/// `None`) with a prefix unique to the solver, and are removed when the
+/// solver is dropped.  The memory used before MKL switches to disk is set
+/// through the environment, see
+/// [`set_out_of_core`](crate::MKLPardisoSolver::set_out_of_core).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutOfCoreConfig {
+    pub mode: OutOfCoreMode,
+    pub directory: Option<PathBuf>,
+}
+
+impl OutOfCoreConfig {
+    pub fn mode(mut self, mode: OutOfCoreMode) -> Self {
+        self.mode = mode;
+        self
+    }
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+}
@@ -0,0 +1,85 @@
+use crate::PardisoError;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, RwLock};
+
+/// Replacement rule for small pivots, see
+/// [`set_pivot_handler`](crate::MKLPardisoSolver::set_pivot_handler).
+///
+/// Called as `handler(aii, bii, eps)` with the diagonal entry `aii` of the
+/// original matrix, the current pivot `bii` and the pivot threshold `eps`.
+/// Returns the value that replaces `bii`, or `None` to keep it.
+pub type PivotHandler = dyn Fn(f64, f64, f64) -> Option<f64> + Send + Sync;
+
+// The installed handler and the id of the solver that owns it.  MKL
+// calls a single mkl_pardiso_pivot for the whole process, so there can
+// only be one
+static HANDLER: RwLock<Option<(usize, Arc<PivotHandler>)>> = RwLock::new(None);
+
+pub(crate) fn install(owner: usize, handler: Arc<PivotHandler>) -> Result<(), PardisoError> {
+    let mut current = HANDLER.write().unwrap_or_else(|e| e.into_inner());
+    match &*current {
+        Some((id, _)) if *id != owner => Err(PardisoError::PivotHandlerInUse),
+        _ => {
+            *current = Some((owner, handler));
+            Ok(())
+        }
+    }
+}
+
+pub(crate) fn clear(owner: usize) {
+    let mut current = HANDLER.write().unwrap_or_else(|e| e.into_inner());
+    if matches!(&*current, Some((id, _)) if *id == owner) {
+        *current = None;
+    }
+}
+
+pub(crate) fn owner() -> Option<usize> {
+    let current = HANDLER.read().unwrap_or_else(|e| e.into_inner());
+    current.as_ref().map(|(id, _)| *id)
+}
+
+// The rule MKL applies without a user hook: pivots with |bii| <= eps
+// are replaced by eps with the sign of bii
+pub(crate) fn default_pivot(bii: f64, eps: f64) -> Option<f64> {
+    if bii > eps || bii < -eps {
+        return None;
+    }
+    Some(if bii > 0.0 { eps } else { -eps })
+}
+
+/// Pivot hook called by MKL Pardiso when `iparm[55] = 1`.
+///
+/// Dispatches to the handler installed with
+/// [`set_pivot_handler`](crate::MKLPardisoSolver::set_pivot_handler), or
+/// applies MKL's default rule if there is none.  The hook replaces the
+/// one in MKL through symbol interposition, so the final executable must
+/// export it (e.g. link with `-rdynamic`); otherwise MKL keeps its
+/// built-in default.
+///
+/// # Safety
+///
+/// Only to be called by MKL, with valid pointers to single values.
+#[no_mangle]
+pub unsafe extern "C" fn mkl_pardiso_pivot(aii: *const f64, bii: *mut f64, eps: *const f64) -> i32 {
+    let (aii, pivot, eps) = (*aii, *bii, *eps);
+
+    let handler = {
+        let current = HANDLER.read().unwrap_or_else(|e| e.into_inner());
+        current.as_ref().map(|(_, f)| Arc::clone(f))
+    };
+
+    // unwinding into MKL is undefined behaviour
+    let replacement = match handler {
+        Some(f) => catch_unwind(AssertUnwindSafe(|| f(aii, pivot, eps)))
+            .unwrap_or_else(|_| std::process::abort()),
+        None => default_pivot(pivot, eps),
+    };
+
+    match replacement {
+        Some(value) => {
+            *bii = value;
+            1
+        }
+        None => 0,
+    }
+}
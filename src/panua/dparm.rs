@@ -0,0 +1,97 @@
+/// Typed view of the Panua `dparm` inputs.
+///
+/// These control the iterative (multi-recursive incomplete factorization
+/// preconditioned Krylov) solver selected with `SolverType::Iterative`.
+/// There is no default, since Panua chooses the defaults in `pardisoinit`.
+/// As with [`IParm`](crate::IParm), read the current options with
+/// `get_dparm_options`, modify them and write them back.
+///
+/// The pivot threshold of the direct solver and the inertia are `iparm`
+/// entries, see [`IParm::pivot_perturbation`](crate::IParm) and
+/// `get_inertia`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DParm {
+    // maximum number of Krylov-subspace iterations
+    pub max_iterations: i32,
+    // relative residual reduction for convergence
+    pub relative_tolerance: f64,
+    // dimension of the coarse grid matrix
+    pub coarse_grid_size: i32,
+    // maximum number of grid levels
+    pub max_grid_levels: i32,
+    // dropping value for the incomplete factor
+    pub factor_drop_tolerance: f64,
+    // dropping value for the Schur complement
+    pub schur_drop_tolerance: f64,
+    // maximum fill-in per column of the factor
+    pub max_fill: i32,
+    // bound on the norm of the inverse of the incomplete factor L
+    pub inverse_norm_bound: f64,
+    // maximum number of non-improving Krylov-subspace iterations
+    pub max_stagnation: i32,
+}
+
+impl DParm {
+    pub fn max_iterations(mut self, iterations: i32) -> Self {
+        self.max_iterations = iterations;
+        self
+    }
+    pub fn relative_tolerance(mut self, tol: f64) -> Self {
+        self.relative_tolerance = tol;
+        self
+    }
+    pub fn coarse_grid_size(mut self, size: i32) -> Self {
+        self.coarse_grid_size = size;
+        self
+    }
+    pub fn max_grid_levels(mut self, levels: i32) -> Self {
+        self.max_grid_levels = levels;
+        self
+    }
+    pub fn factor_drop_tolerance(mut self, tol: f64) -> Self {
+        self.factor_drop_tolerance = tol;
+        self
+    }
+    pub fn schur_drop_tolerance(mut self, tol: f64) -> Self {
+        self.schur_drop_tolerance = tol;
+        self
+    }
+    pub fn max_fill(mut self, fill: i32) -> Self {
+        self.max_fill = fill;
+        self
+    }
+    pub fn inverse_norm_bound(mut self, bound: f64) -> Self {
+        self.inverse_norm_bound = bound;
+        self
+    }
+    pub fn max_stagnation(mut self, iterations: i32) -> Self {
+        self.max_stagnation = iterations;
+        self
+    }
+
+    pub(crate) fn write(&self, dparm: &mut [f64; 64]) {
+        dparm[0] = self.max_iterations as f64;
+        dparm[1] = self.relative_tolerance;
+        dparm[2] = self.coarse_grid_size as f64;
+        dparm[3] = self.max_grid_levels as f64;
+        dparm[4] = self.factor_drop_tolerance;
+        dparm[5] = self.schur_drop_tolerance;
+        dparm[6] = self.max_fill as f64;
+        dparm[7] = self.inverse_norm_bound;
+        dparm[8] = self.max_stagnation as f64;
+    }
+
+    pub(crate) fn read(dparm: &[f64; 64]) -> Self {
+        Self {
+            max_iterations: dparm[0] as i32,
+            relative_tolerance: dparm[1],
+            coarse_grid_size: dparm[2] as i32,
+            max_grid_levels: dparm[3] as i32,
+            factor_drop_tolerance: dparm[4],
+            schur_drop_tolerance: dparm[5],
+            max_fill: dparm[6] as i32,
+            inverse_norm_bound: dparm[7],
+            max_stagnation: dparm[8] as i32,
+        }
+    }
+}
@@ -10,8 +10,11 @@ pub(crate) struct PanuaPardisoPointers<'a> {
     pub pardiso_chkmatrix: Symbol<'a, PARDISO_CHKMATRIX>,
     pub pardiso_chkvec: Symbol<'a, PARDISO_CHKVEC>,
     pub pardiso_printstats: Symbol<'a, PARDISO_PRINTSTATS>,
+    pub pardiso_get_schur: Option<Symbol<'a, PARDISO_GET_SCHUR>>,
 }
 
+// the matrix values, rhs and solution are untyped since
+// they may be real or complex
 pub(crate) type PARDISO = extern "C" fn(
     pt: *mut c_void,
     maxfct: *const i32,
@@ -19,15 +22,15 @@ pub(crate) type PARDISO = extern "C" fn(
     mtype: *const i32,
     phase: *const i32,
     n: *const i32,
-    a: *const f64,
+    a: *const c_void,
     ia: *const i32,
     ja: *const i32,
     perm: *mut i32,
     nrhs: *const i32,
     iparm: *mut i32,
     msglvl: *const i32,
-    b: *mut f64,
-    x: *mut f64,
+    b: *mut c_void,
+    x: *mut c_void,
     error: *mut i32,
     dparm: *mut f64,
 );
@@ -63,3 +66,15 @@ pub(crate) type PARDISO_PRINTSTATS = extern "C" fn(
     b: *const f64,
     error: *mut i32,
 );
+
+// copies the Schur complement computed in phase 12 with iparm[37] > 0
+// into CSR arrays.  The number of nonzeros is returned in iparm[38]
+pub(crate) type PARDISO_GET_SCHUR = extern "C" fn(
+    pt: *mut c_void,
+    maxfct: *const i32,
+    mnum: *const i32,
+    mtype: *const i32,
+    s: *mut f64,
+    is: *mut i32,
+    js: *mut i32,
+);
@@ -1,11 +1,41 @@
+use super::dparm::DParm;
 use super::loader::*;
-use crate::enums::{MatrixType, SolverType};
-use crate::{PanuaPardisoError, PardisoData, PardisoError, PardisoInterface};
+use super::selinv::SelectedInverse;
+use crate::dense::schur_block;
+use crate::enums::{MatrixType, Ordering, Phase, Precision, Sign, SolverType, Transpose};
+use crate::{
+    Complex64, CsrMatrix, DenseMatrix, IParm, IndexBase, PanuaPardisoError, PardisoData,
+    PardisoError, PardisoInterface, PardisoScalar, SolverStats,
+};
+use std::collections::BTreeMap;
 use std::ffi::c_void;
 
 pub struct PanuaPardisoSolver {
     _data: PardisoData,
     _dparm: [f64; 64],
+    // one-based copy of the structure of the last zero-based matrix
+    _one_based: (Vec<i32>, Vec<i32>),
+    // ln|det(A)| from the last factorization of each matrix number
+    _log_det: BTreeMap<i32, f64>,
+}
+
+// Panua only accepts one-based indices, so zero-based matrices are
+// passed with a one-based copy of their structure.  The copy in `cached`
+// is reused while the pattern is unchanged, otherwise a new one is made
+// in `scratch`
+fn one_based<'b, T: PardisoScalar>(
+    matrix: &CsrMatrix<'b, T>,
+    cached: &'b (Vec<i32>, Vec<i32>),
+    scratch: &'b mut Option<(Vec<i32>, Vec<i32>)>,
+) -> CsrMatrix<'b, T> {
+    if matrix.base() == IndexBase::One {
+        return *matrix;
+    }
+    let (ia, ja) = match matrix.has_one_based_copy(cached) {
+        true => cached,
+        false => &*scratch.insert(matrix.one_based_indices().expect("zero-based matrix")),
+    };
+    matrix.with_indices(ia, ja)
 }
 
 impl PanuaPardisoSolver {
@@ -43,6 +73,138 @@ impl PanuaPardisoSolver {
     pub fn set_dparm(&mut self, i: usize, value: f64) {
         self._dparm[i] = value;
     }
+
+    // typed iterative solver options, built from the current dparm.
+    // Read them after pardisoinit to get the defaults
+    pub fn set_dparm_options(&mut self, options: &DParm) {
+        options.write(&mut self._dparm);
+    }
+    pub fn get_dparm_options(&self) -> DParm {
+        DParm::read(&self._dparm)
+    }
+
+    // outputs written by Panua after factorization or solve.  The
+    // determinant is only computed when iparm[32] = 1, and is given
+    // as its natural logarithm
+    pub fn get_log_determinant(&self) -> f64 {
+        self._dparm[32]
+    }
+    pub fn get_relative_residual(&self) -> f64 {
+        self._dparm[33]
+    }
+    pub fn get_num_krylov_iterations(&self) -> i32 {
+        self._dparm[34] as i32
+    }
+}
+
+impl PanuaPardisoSolver {
+    // Common implementation for all scalar types.  The precision
+    // of the factorization is given separately since Panua always
+    // takes double precision data, see pardiso_f32.  Permutations are
+    // handed back in the index base of the input
+    fn pardiso_generic<T: PardisoScalar>(
+        &mut self,
+        matrix: &CsrMatrix<T>,
+        b: &mut [T],
+        x: &mut [T],
+        nrhs: i32,
+        precision: Precision,
+        inverse: Option<&mut [T]>,
+    ) -> Result<(), PardisoError> {
+        let base = matrix.base();
+        let cached = std::mem::take(&mut self._one_based);
+        let mut scratch = None;
+        let matrix = one_based(matrix, &cached, &mut scratch);
+        let result = self.pardiso_impl(&matrix, b, x, nrhs, precision, inverse, base);
+        self._one_based = scratch.unwrap_or(cached);
+        result
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn pardiso_impl<T: PardisoScalar>(
+        &mut self,
+        matrix: &CsrMatrix<T>,
+        b: &mut [T],
+        x: &mut [T],
+        nrhs: i32,
+        precision: Precision,
+        inverse: Option<&mut [T]>,
+        base: IndexBase,
+    ) -> Result<(), PardisoError> {
+        let ptrs = panua_ptrs()?;
+
+        // selected inversion overwrites the values of the matrix, so
+        // it is only allowed on a separate buffer, see selected_inverse
+        if self.data().phase == Phase::SelectedInversion && inverse.is_none() {
+            return Err(PardisoError::UnsupportedOption {
+                backend: "panua",
+                option: "Selected inversion through pardiso()".to_string(),
+            });
+        }
+
+        self.data().check_scalar::<T>()?;
+        self.data().check_precision(precision)?;
+        self.data().check_buffers(matrix.n(), nrhs, b, x)?;
+        self.data_mut().prepare_perm(matrix.n(), matrix.base())?;
+
+        let phase = self.data().phase;
+        if !matches!(phase, Phase::ReleaseAll | Phase::ReleaseLUandMNUM) {
+            self.ensure_iparm_initialized()?;
+            self.data_mut().iparm[28] = (precision == Precision::Single) as i32;
+        }
+
+        // compute the determinant with every factorization, see
+        // log_determinant.  The user setting is restored afterwards
+        let compute_det = self.data().iparm[32];
+        if phase.includes_factorization() {
+            self.data_mut().iparm[32] = 1;
+        }
+
+        let mut error = 0;
+        let pt = self.data_mut().pt.as_mut_ptr() as *mut c_void;
+        let maxfct = self.data().maxfct;
+        let mnum = self.data().mnum;
+        let mtype = self.get_matrix_type() as i32;
+        let phase = phase as i32;
+        let n = matrix.n() as i32;
+        let a = match inverse {
+            Some(values) => values.as_mut_ptr() as *const c_void,
+            None => matrix.values().as_ptr() as *const c_void,
+        };
+        let ia = matrix.row_ptrs().as_ptr();
+        let ja = matrix.col_indices().as_ptr();
+        let b = b.as_mut_ptr() as *mut c_void;
+        let x = x.as_mut_ptr() as *mut c_void;
+        let perm = self.data_mut().perm.as_mut_ptr();
+        let iparm = self.data_mut().iparm.as_mut_ptr();
+        let msglvl = self.data().msglvl as i32;
+        let dparm = self._dparm.as_mut_ptr();
+
+        (ptrs.pardiso)(
+            pt, &maxfct, &mnum, &mtype, &phase, &n, a, ia, ja, perm, &nrhs, iparm, &msglvl, b, x,
+            &mut error, dparm,
+        );
+        self.data_mut().iparm[32] = compute_det;
+
+        if error != 0 {
+            let error = PanuaPardisoError::from(error);
+            return Err(PardisoError::from(error));
+        }
+        if !matches!(
+            self.data().phase,
+            Phase::ReleaseAll | Phase::ReleaseLUandMNUM
+        ) {
+            self.data_mut().rebase_perm(base);
+        }
+        self.data_mut().record_call(precision, matrix.n());
+
+        let factored = &self._data.factored;
+        self._log_det.retain(|mnum, _| factored.contains_key(mnum));
+        if self.data().phase.includes_factorization() {
+            self._log_det.insert(self.data().mnum, self._dparm[32]);
+        }
+        Ok(())
+    }
 }
 
 impl PardisoInterface for PanuaPardisoSolver {
@@ -53,6 +215,21 @@ impl PardisoInterface for PanuaPardisoSolver {
         &mut self._data
     }
 
+    // Entries set by the wrapper are only read when iparm[0] = 1.  With
+    // iparm[0] = 0 Panua would replace iparm and dparm with its defaults,
+    // so those defaults are filled in first
+    fn ensure_iparm_initialized(&mut self) -> Result<(), PardisoError> {
+        if self.data().iparm[0] == 0 {
+            let mut data = PardisoData::default();
+            let mtype = self.get_matrix_type();
+            let solver = self.get_solver();
+            Self::pardisoinit_impl(&mut data, &mut self._dparm, mtype, solver)?;
+            self.data_mut().iparm = data.iparm;
+        }
+        self.data_mut().iparm[0] = 1;
+        Ok(())
+    }
+
     fn new() -> Result<Self, PardisoError> {
         if !PanuaPardisoSolver::is_loaded() {
             return Err(PanuaPardisoError::LibraryLoadFailure)?;
@@ -66,6 +243,8 @@ impl PardisoInterface for PanuaPardisoSolver {
         Ok(Self {
             _data: data,
             _dparm: dparm,
+            _one_based: (vec![], vec![]),
+            _log_det: BTreeMap::new(),
         })
     }
 
@@ -82,40 +261,48 @@ impl PardisoInterface for PanuaPardisoSolver {
 
     fn pardiso(
         &mut self,
-        a: &[f64],
-        ia: &[i32],
-        ja: &[i32],
+        matrix: &CsrMatrix,
         b: &mut [f64],
         x: &mut [f64],
-        n: i32,
         nrhs: i32,
     ) -> Result<(), PardisoError> {
-        let ptrs = panua_ptrs()?;
-
-        let mut error = 0;
-        let pt = self.data_mut().pt.as_mut_ptr() as *mut c_void;
-        let maxfct = self.data().maxfct;
-        let mnum = self.data().mnum;
-        let mtype = self.get_matrix_type() as i32;
-        let phase = self.data().phase as i32;
-        let a = a.as_ptr();
-        let ia = ia.as_ptr();
-        let ja = ja.as_ptr();
-        let b = b.as_mut_ptr();
-        let x = x.as_mut_ptr();
-        let perm = self.data_mut().perm.as_mut_ptr();
-        let iparm = self.data_mut().iparm.as_mut_ptr();
-        let msglvl = self.data().msglvl as i32;
-        let dparm = self._dparm.as_mut_ptr();
+        self.pardiso_generic(matrix, b, x, nrhs, Precision::Double, None)
+    }
 
-        (ptrs.pardiso)(
-            pt, &maxfct, &mnum, &mtype, &phase, &n, a, ia, ja, perm, &nrhs, iparm, &msglvl, b, x,
-            &mut error, dparm,
-        );
+    fn pardiso_complex(
+        &mut self,
+        matrix: &CsrMatrix<Complex64>,
+        b: &mut [Complex64],
+        x: &mut [Complex64],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        self.pardiso_generic(matrix, b, x, nrhs, Precision::Double, None)
+    }
 
-        if error != 0 {
-            let error = PanuaPardisoError::from(error);
-            return Err(PardisoError::from(error));
+    fn pardiso_f32(
+        &mut self,
+        matrix: &CsrMatrix<f32>,
+        b: &mut [f32],
+        x: &mut [f32],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        // Panua computes a 32-bit factorization (iparm[28] = 1) from
+        // double precision data, so widen the inputs and narrow the
+        // outputs.  b is copied back since it holds the solution
+        // when solving in place
+        let widen = |v: &[f32]| v.iter().map(|&v| v as f64).collect::<Vec<f64>>();
+        let a = widen(matrix.values());
+        let matrix = matrix.with_values(&a)?;
+        let mut b64 = widen(b);
+        let mut x64 = widen(x);
+
+        self.pardiso_generic(&matrix, &mut b64, &mut x64, nrhs, Precision::Single, None)?;
+
+        for (dst, src) in b.iter_mut().zip(b64) {
+            *dst = src as f32;
+        }
+        for (dst, src) in x.iter_mut().zip(x64) {
+            *dst = src as f32;
         }
         Ok(())
     }
@@ -124,6 +311,111 @@ impl PardisoInterface for PanuaPardisoSolver {
         "panua"
     }
 
+    fn set_ordering(&mut self, ordering: Ordering) -> Result<(), PardisoError> {
+        if ordering == Ordering::ParallelMetis {
+            return Err(PardisoError::UnsupportedOption {
+                backend: "panua",
+                option: format!("{ordering} ordering"),
+            });
+        }
+        self.ensure_iparm_initialized()?;
+        let iparm = &mut self.data_mut().iparm;
+        match ordering {
+            Ordering::User => iparm[4] = 1,
+            _ => {
+                iparm[1] = match ordering {
+                    Ordering::Amd => 0,
+                    _ => 2,
+                };
+                // stop using any user permutation
+                if iparm[4] == 1 {
+                    iparm[4] = 0;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_ordering(&self) -> Ordering {
+        let iparm = &self.data().iparm;
+        match (iparm[4], iparm[1]) {
+            (1, _) => Ordering::User,
+            (_, 0) => Ordering::Amd,
+            _ => Ordering::Metis,
+        }
+    }
+
+    fn set_iparm_options(&mut self, options: &IParm) -> Result<(), PardisoError> {
+        self.ensure_iparm_initialized()?;
+        self.set_ordering(options.ordering)?;
+
+        let transpose = self.transpose_code(options.transpose);
+        let iparm = &mut self.data_mut().iparm;
+        options.write_common(iparm);
+        iparm[11] = transpose;
+        Ok(())
+    }
+
+    fn get_iparm_options(&self) -> IParm {
+        let iparm = &self.data().iparm;
+        let transpose = match iparm[11] {
+            1 => Transpose::Transpose,
+            2 => Transpose::ConjugateTranspose,
+            _ => Transpose::None,
+        };
+        IParm::read_common(iparm, self.get_ordering(), transpose)
+    }
+
+    fn transpose_code(&self, transpose: Transpose) -> i32 {
+        // NB: Panua uses 1 for the plain transpose
+        match transpose {
+            Transpose::None => 0,
+            Transpose::Transpose => 1,
+            Transpose::ConjugateTranspose => 2,
+        }
+    }
+
+    fn get_stats(&self) -> SolverStats {
+        // as for MKL, a nonpositive factor size, operation count or
+        // memory size means the phase computing it has not run
+        let iparm = &self.data().iparm;
+        let memory = |v: i32| (v > 0).then_some(v);
+        let reported = |v: i32| (v > 0).then_some(v.into());
+        SolverStats {
+            phase: self.data().phase,
+            peak_analysis_memory_kb: memory(iparm[14]),
+            permanent_memory_kb: memory(iparm[15]),
+            factor_memory_kb: memory(iparm[16]),
+            factor_nnz: reported(iparm[17]),
+            factor_mflop: reported(iparm[18]),
+            perturbed_pivots: iparm[13],
+            refinement_steps: iparm[6],
+        }
+    }
+
+    fn log_determinant(&self) -> Result<(f64, Sign), PardisoError> {
+        self.data().check_factored()?;
+        let mtype = self.get_matrix_type();
+        if !(mtype.is_real() && mtype.is_symmetric()) {
+            return Err(PardisoError::UnsupportedOption {
+                backend: "panua",
+                option: format!("Determinant of matrix type {mtype}"),
+            });
+        }
+        // ln|det(A)| is taken from dparm[32] after each factorization,
+        // and the sign follows from the number of negative eigenvalues
+        let mnum = self.data().mnum;
+        let logdet = self._log_det[&mnum];
+        let sign = if logdet == f64::NEG_INFINITY {
+            Sign::Zero
+        } else if self.data().factored[&mnum].negative % 2 == 1 {
+            Sign::Negative
+        } else {
+            Sign::Positive
+        };
+        Ok((logdet, sign))
+    }
+
     fn is_licensed() -> bool {
         crate::panua::interface::panua_is_licensed()
     }
@@ -142,18 +434,19 @@ impl PanuaPardisoSolver {
     pub fn pardiso_chkmatrix(
         &self,
         mtype: MatrixType,
-        n: i32,
-        a: &[f64],
-        ia: &[i32],
-        ja: &[i32],
+        matrix: &CsrMatrix,
     ) -> Result<(), PanuaPardisoError> {
         let ptrs = panua_ptrs()?;
 
+        let mut scratch = None;
+        let matrix = one_based(matrix, &self._one_based, &mut scratch);
+
         let mut error = 0;
         let mtype = mtype as i32;
-        let a = a.as_ptr();
-        let ia = ia.as_ptr();
-        let ja = ja.as_ptr();
+        let n = matrix.n() as i32;
+        let a = matrix.values().as_ptr();
+        let ia = matrix.row_ptrs().as_ptr();
+        let ja = matrix.col_indices().as_ptr();
 
         (ptrs.pardiso_chkmatrix)(&mtype, &n, a, ia, ja, &mut error);
 
@@ -177,24 +470,24 @@ impl PanuaPardisoSolver {
         Ok(())
     }
 
-    #[allow(clippy::too_many_arguments)]
     pub fn pardiso_printstats(
         &self,
         mtype: MatrixType,
-        n: i32,
-        a: &[f64],
-        ia: &[i32],
-        ja: &[i32],
+        matrix: &CsrMatrix,
         nrhs: i32,
         b: &[f64],
     ) -> Result<(), PanuaPardisoError> {
         let ptrs = panua_ptrs()?;
 
+        let mut scratch = None;
+        let matrix = one_based(matrix, &self._one_based, &mut scratch);
+
         let mut error = 0;
         let mtype = mtype as i32;
-        let a = a.as_ptr();
-        let ia = ia.as_ptr();
-        let ja = ja.as_ptr();
+        let n = matrix.n() as i32;
+        let a = matrix.values().as_ptr();
+        let ia = matrix.row_ptrs().as_ptr();
+        let ja = matrix.col_indices().as_ptr();
         let b = b.as_ptr();
 
         (ptrs.pardiso_printstats)(&mtype, &n, a, ia, ja, &nrhs, b, &mut error);
@@ -206,6 +499,135 @@ impl PanuaPardisoSolver {
     }
 }
 
+impl PanuaPardisoSolver {
+    /// Computes the dense Schur complement of the block of rows and
+    /// columns given by `block_indices` (zero-based, in any order).
+    ///
+    /// Panua only supports a trailing block, i.e. the indices must be
+    /// `n - k..n` for some `k`.  Row and column `i` of the result
+    /// correspond to row and column `n - k + i` of the matrix.  This runs
+    /// `Phase::AnalysisNumFact`, after which the handle holds a partial
+    /// factorization.  Methods that need the factorization return
+    /// `NotFactorized` until the matrix has been factorized again.
+    pub fn schur_complement(
+        &mut self,
+        matrix: &CsrMatrix,
+        block_indices: &[usize],
+    ) -> Result<DenseMatrix, PardisoError> {
+        let n = matrix.n();
+        let block = schur_block(n, block_indices)?;
+        let k = block.len();
+        if block.iter().enumerate().any(|(i, &j)| j != n - k + i) {
+            return Err(PardisoError::UnsupportedOption {
+                backend: "panua",
+                option: "Schur complement of a non-trailing block".to_string(),
+            });
+        }
+        let get_schur = panua_ptrs()?.pardiso_get_schur.as_ref().ok_or_else(|| {
+            PardisoError::UnsupportedOption {
+                backend: "panua",
+                option: "Schur complement without pardiso_get_schur".to_string(),
+            }
+        })?;
+
+        // iparm is initialized first, so that the order of the block is
+        // not replaced by the defaults
+        self.ensure_iparm_initialized()?;
+        self.data_mut().iparm[37] = k as i32;
+        self.set_phase(Phase::AnalysisNumFact);
+        let result = self.pardiso(matrix, &mut [], &mut [], 1);
+        let data = self.data_mut();
+        data.factored.remove(&data.mnum);
+        if let Err(e) = result {
+            data.iparm[37] = 0;
+            return Err(e);
+        }
+
+        // iparm[38] holds the number of nonzeros of the complement, which
+        // is positive if it has been computed
+        if self.data().iparm[38] <= 0 {
+            self.data_mut().iparm[37] = 0;
+            return Err(PardisoError::SchurNotComputed(k));
+        }
+
+        // the complement is returned in CSR format
+        let nnz = self.data().iparm[38] as usize;
+        let mut s = vec![0.0; nnz];
+        let mut is = vec![0; k + 1];
+        let mut js = vec![0; nnz];
+        let pt = self.data_mut().pt.as_mut_ptr() as *mut c_void;
+        let maxfct = self.data().maxfct;
+        let mnum = self.data().mnum;
+        let mtype = self.get_matrix_type();
+
+        get_schur(
+            pt,
+            &maxfct,
+            &mnum,
+            &(mtype as i32),
+            s.as_mut_ptr(),
+            is.as_mut_ptr(),
+            js.as_mut_ptr(),
+        );
+        self.data_mut().iparm[37] = 0;
+
+        // expand to dense, mirroring the stored triangle of symmetric
+        // matrices
+        let base = is[0];
+        let mut dense = vec![0.0; k * k];
+        for row in 0..k {
+            for idx in (is[row] - base) as usize..(is[row + 1] - base) as usize {
+                let col = (js[idx] - base) as usize;
+                dense[row * k + col] = s[idx];
+                if mtype.is_symmetric() {
+                    dense[col * k + row] = s[idx];
+                }
+            }
+        }
+        Ok(DenseMatrix::from_row_major(k, k, dense))
+    }
+}
+
+impl PanuaPardisoSolver {
+    /// Computes the entries of the inverse of a real matrix on its
+    /// sparsity pattern.
+    ///
+    /// The numerical factorization of `matrix` must have been computed
+    /// already.  The factorization is kept, so the solver can still be
+    /// used to solve with `matrix` afterwards.
+    pub fn selected_inverse<'a>(
+        &mut self,
+        matrix: &CsrMatrix<'a>,
+    ) -> Result<SelectedInverse<'a>, PardisoError> {
+        self.data().check_factored()?;
+        let mut values = matrix.values().to_vec();
+
+        // iparm[35] = 1 writes the inverse to the values instead of
+        // overwriting the internal factor
+        let keep_factor = self.data().iparm[35];
+        self.data_mut().iparm[35] = 1;
+        self.set_phase(Phase::SelectedInversion);
+        let result = self.pardiso_generic(
+            matrix,
+            &mut [],
+            &mut [],
+            1,
+            Precision::Double,
+            Some(&mut values),
+        );
+        self.data_mut().iparm[35] = keep_factor;
+        result?;
+
+        Ok(SelectedInverse::new(*matrix, values))
+    }
+
+    // diagonal of the inverse, e.g. the marginal variances of a
+    // Gaussian Markov random field with precision matrix `matrix`
+    pub fn diag_of_inverse(&mut self, matrix: &CsrMatrix) -> Result<Vec<f64>, PardisoError> {
+        Ok(self.selected_inverse(matrix)?.diagonal())
+    }
+}
+
 impl Drop for PanuaPardisoSolver {
     fn drop(&mut self) {
         self.release();
@@ -82,6 +82,8 @@ lazy_static! {
         let pardiso_chkmatrix: Symbol<PARDISO_CHKMATRIX> = unsafe { lib.get::<PARDISO_CHKMATRIX>(b"pardiso_chkmatrix_").ok()? };
         let pardiso_chkvec: Symbol<PARDISO_CHKVEC> = unsafe { lib.get::<PARDISO_CHKVEC>(b"pardiso_chkvec_").ok()? };
         let pardiso_printstats: Symbol<PARDISO_PRINTSTATS> = unsafe { lib.get::<PARDISO_PRINTSTATS>(b"pardiso_printstats_").ok()? };
+        // not exported by all library versions
+        let pardiso_get_schur: Option<Symbol<PARDISO_GET_SCHUR>> = unsafe { lib.get::<PARDISO_GET_SCHUR>(b"pardiso_get_schur").ok() };
 
         Some(PanuaPardisoPointers {
             pardiso,
@@ -89,6 +91,7 @@ lazy_static! {
             pardiso_chkmatrix,
             pardiso_chkvec,
             pardiso_printstats,
+            pardiso_get_schur,
         })
     };
 
@@ -1,4 +1,8 @@
+pub(crate) mod dparm;
 pub(crate) mod ffi;
 pub(crate) mod interface;
 pub(crate) mod loader;
+pub(crate) mod selinv;
+pub use dparm::*;
 pub use interface::*;
+pub use selinv::*;
@@ -0,0 +1,50 @@
+use crate::CsrMatrix;
+
+/// Entries of the inverse of a factorized matrix on its sparsity pattern.
+///
+/// Returned by
+/// [`selected_inverse`](crate::PanuaPardisoSolver::selected_inverse).
+/// The values are ordered as the values of the original matrix, so that
+/// for a symmetric matrix stored as its upper triangle only the upper
+/// triangle of the inverse is available.
+#[derive(Debug, Clone)]
+pub struct SelectedInverse<'a> {
+    pattern: CsrMatrix<'a>,
+    values: Vec<f64>,
+}
+
+impl<'a> SelectedInverse<'a> {
+    pub(crate) fn new(pattern: CsrMatrix<'a>, values: Vec<f64>) -> Self {
+        Self { pattern, values }
+    }
+
+    // the inverse entries as a matrix on the original pattern
+    pub fn matrix(&self) -> CsrMatrix<'_> {
+        self.pattern
+            .with_values(&self.values)
+            .expect("one value per pattern entry")
+    }
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+    pub fn into_values(self) -> Vec<f64> {
+        self.values
+    }
+
+    // Diagonal of the inverse.  Diagonal entries missing from the
+    // pattern are returned as zero
+    pub fn diagonal(&self) -> Vec<f64> {
+        let base = self.pattern.base() as i32;
+        let ia = self.pattern.row_ptrs();
+        let ja = self.pattern.col_indices();
+        (0..self.pattern.n())
+            .map(|row| {
+                let start = (ia[row] - base) as usize;
+                let stop = (ia[row + 1] - base) as usize;
+                (start..stop)
+                    .find(|&k| (ja[k] - base) as usize == row)
+                    .map_or(0.0, |k| self.values[k])
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{IndexBase, PardisoError};
+
+/// A fill-in reducing permutation of `base..n + base`.
+///
+/// A permutation can be supplied to the solver as a user ordering with
+/// [`set_perm`](crate::PardisoInterface::set_perm), or the ordering
+/// computed during analysis can be read back with
+/// [`get_permutation`](crate::PardisoInterface::get_permutation).  The
+/// latter makes it possible to reuse an expensive ordering, e.g. in
+/// another process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permutation {
+    perm: Vec<i32>,
+    base: IndexBase,
+}
+
+impl Permutation {
+    pub fn new(perm: Vec<i32>, base: IndexBase) -> Result<Self, PardisoError> {
+        let n = perm.len();
+        let mut seen = vec![false; n];
+        for (index, &p) in perm.iter().enumerate() {
+            let i = p as i64 - base as i64;
+            if i < 0 || i >= n as i64 || seen[i as usize] {
+                return Err(PardisoError::InvalidPermutation {
+                    index,
+                    value: p as i64,
+                });
+            }
+            seen[i as usize] = true;
+        }
+        Ok(Self { perm, base })
+    }
+
+    // permutation data written by Pardiso, assumed to be valid
+    pub(crate) fn new_unchecked(perm: Vec<i32>, base: IndexBase) -> Self {
+        Self { perm, base }
+    }
+
+    pub fn len(&self) -> usize {
+        self.perm.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.perm.is_empty()
+    }
+    pub fn base(&self) -> IndexBase {
+        self.base
+    }
+    pub fn as_slice(&self) -> &[i32] {
+        &self.perm
+    }
+    pub fn into_vec(self) -> Vec<i32> {
+        self.perm
+    }
+
+    // the same permutation with a different index base
+    pub fn to_base(&self, base: IndexBase) -> Self {
+        let shift = base as i32 - self.base as i32;
+        Self {
+            perm: self.perm.iter().map(|&p| p + shift).collect(),
+            base,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{CsrMatrix, PardisoError, PardisoInterface, Precision};
+use num_complex::Complex64;
+
+mod private {
+    pub trait Sealed {}
+    impl Sealed for f64 {}
+    impl Sealed for f32 {}
+    impl Sealed for num_complex::Complex64 {}
+}
+
+/// Scalar types accepted by the Pardiso solvers.
+///
+/// This trait is sealed since matrix and vector data are passed to the
+/// library as untyped pointers, and only layouts that Pardiso understands
+/// can be allowed.
+pub trait PardisoScalar: private::Sealed + Copy + Default + std::fmt::Debug + 'static {
+    const IS_COMPLEX: bool;
+    const PRECISION: Precision;
+    const NAME: &'static str;
+
+    // dispatch to the trait entry point for this scalar type
+    #[doc(hidden)]
+    fn call_pardiso<P: PardisoInterface + ?Sized>(
+        ps: &mut P,
+        matrix: &CsrMatrix<Self>,
+        b: &mut [Self],
+        x: &mut [Self],
+        nrhs: i32,
+    ) -> Result<(), PardisoError>;
+}
+
+impl PardisoScalar for f64 {
+    const IS_COMPLEX: bool = false;
+    const PRECISION: Precision = Precision::Double;
+    const NAME: &'static str = "f64";
+
+    fn call_pardiso<P: PardisoInterface + ?Sized>(
+        ps: &mut P,
+        matrix: &CsrMatrix<Self>,
+        b: &mut [Self],
+        x: &mut [Self],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        ps.pardiso(matrix, b, x, nrhs)
+    }
+}
+
+impl PardisoScalar for f32 {
+    const IS_COMPLEX: bool = false;
+    const PRECISION: Precision = Precision::Single;
+    const NAME: &'static str = "f32";
+
+    fn call_pardiso<P: PardisoInterface + ?Sized>(
+        ps: &mut P,
+        matrix: &CsrMatrix<Self>,
+        b: &mut [Self],
+        x: &mut [Self],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        ps.pardiso_f32(matrix, b, x, nrhs)
+    }
+}
+
+impl PardisoScalar for Complex64 {
+    const IS_COMPLEX: bool = true;
+    const PRECISION: Precision = Precision::Double;
+    const NAME: &'static str = "Complex64";
+
+    fn call_pardiso<P: PardisoInterface + ?Sized>(
+        ps: &mut P,
+        matrix: &CsrMatrix<Self>,
+        b: &mut [Self],
+        x: &mut [Self],
+        nrhs: i32,
+    ) -> Result<(), PardisoError> {
+        ps.pardiso_complex(matrix, b, x, nrhs)
+    }
+}
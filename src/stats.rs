@@ -0,0 +1,107 @@
+use crate::Phase;
+
+/// Solver statistics reported in `iparm` after a call to `pardiso`.
+///
+/// Entries are only meaningful once the phase that computes them has run,
+/// e.g. the factor size after analysis and the refinement steps after a
+/// solve.  Entries the backend did not report are `None`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolverStats {
+    // phase of the call that produced these statistics
+    pub phase: Phase,
+    // peak memory during analysis (kB)
+    pub peak_analysis_memory_kb: Option<i32>,
+    // permanent memory held from analysis (kB)
+    pub permanent_memory_kb: Option<i32>,
+    // memory for numerical factorization and solve (kB)
+    pub factor_memory_kb: Option<i32>,
+    // number of nonzeros in the factors.  64-bit since it can exceed
+    // i32 for matrices solved with 64-bit indices
+    pub factor_nnz: Option<i64>,
+    // floating point operations for the factorization (millions)
+    pub factor_mflop: Option<i64>,
+    // number of perturbed pivots
+    pub perturbed_pivots: i32,
+    // number of iterative refinement steps performed
+    pub refinement_steps: i32,
+}
+
+impl SolverStats {
+    // total peak memory over all phases (kB)
+    pub fn peak_memory_kb(&self) -> Option<i32> {
+        match (
+            self.peak_analysis_memory_kb,
+            self.permanent_memory_kb,
+            self.factor_memory_kb,
+        ) {
+            (Some(p), Some(q), Some(r)) => Some(p.max(q + r)),
+            (p, _, _) => p,
+        }
+    }
+}
+
+impl std::fmt::Display for SolverStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn show<V: ToString>(v: Option<V>) -> String {
+            v.map_or("n/a".to_string(), |v| v.to_string())
+        }
+        writeln!(f, "Pardiso statistics after {}:", self.phase)?;
+        writeln!(
+            f,
+            "  peak memory (analysis):    {} kB",
+            show(self.peak_analysis_memory_kb)
+        )?;
+        writeln!(
+            f,
+            "  permanent memory:          {} kB",
+            show(self.permanent_memory_kb)
+        )?;
+        writeln!(
+            f,
+            "  factorization memory:      {} kB",
+            show(self.factor_memory_kb)
+        )?;
+        writeln!(f, "  nonzeros in factors:       {}", show(self.factor_nnz))?;
+        writeln!(
+            f,
+            "  factorization MFLOP:       {}",
+            show(self.factor_mflop)
+        )?;
+        writeln!(f, "  perturbed pivots:          {}", self.perturbed_pivots)?;
+        write!(f, "  refinement steps:          {}", self.refinement_steps)
+    }
+}
+
+/// Inertia of a symmetric or Hermitian matrix from its factorization.
+///
+/// The counts are taken from the most recent successful numerical
+/// factorization.  Pivots that were perturbed during the factorization
+/// are reported separately since they usually indicate a (nearly)
+/// singular matrix even when `zero` is not positive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Inertia {
+    // number of positive eigenvalues
+    pub positive: i32,
+    // number of negative eigenvalues
+    pub negative: i32,
+    // number of zero eigenvalues, i.e. n - positive - negative
+    pub zero: i32,
+    // number of perturbed pivots
+    pub perturbed_pivots: i32,
+}
+
+impl Inertia {
+    pub fn is_singular(&self) -> bool {
+        self.zero > 0
+    }
+}
+
+impl std::fmt::Display for Inertia {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {}, {}), {} perturbed pivots",
+            self.positive, self.negative, self.zero, self.perturbed_pivots
+        )
+    }
+}
@@ -44,3 +44,304 @@ fn test_get_set_num_threads_mkl() {
     let n = ps.get_num_threads().unwrap();
     assert!(n == 4, "Number of threads should be greater than 0");
 }
+
+#[test]
+fn test_check_buffers() {
+    use crate::*;
+    let mut data = PardisoData::default();
+    let n = 4;
+
+    // analysis and factorization do not need any buffers
+    data.phase = Phase::Analysis;
+    assert!(data.check_buffers::<f64>(n, 1, &[], &[]).is_ok());
+    data.phase = Phase::NumFact;
+    assert!(data.check_buffers::<f64>(n, 3, &[], &[]).is_ok());
+
+    // solve phases need n*nrhs entries in both b and x
+    data.phase = Phase::SolveIterativeRefine;
+    let b = vec![0.0; 12];
+    let x = vec![0.0; 12];
+    assert!(data.check_buffers(n, 3, &b, &x).is_ok());
+    assert!(matches!(
+        data.check_buffers(n, 3, &b[..11], &x),
+        Err(PardisoError::RhsLength {
+            expected: 12,
+            found: 11
+        })
+    ));
+    assert!(matches!(
+        data.check_buffers(n, 3, &b, &x[..4]),
+        Err(PardisoError::SolutionLength { .. })
+    ));
+    assert!(matches!(
+        data.check_buffers(n, 0, &b, &x),
+        Err(PardisoError::InvalidNumRhs(0))
+    ));
+}
+
+#[test]
+fn test_check_scalar() {
+    use crate::*;
+    let mut data = PardisoData {
+        mtype: MatrixType::RealSymmetricIndefinite,
+        ..Default::default()
+    };
+    assert!(data.check_scalar::<f64>().is_ok());
+    assert!(data.check_scalar::<Complex64>().is_err());
+
+    data.mtype = MatrixType::ComplexHermitianIndefinite;
+    assert!(data.check_scalar::<Complex64>().is_ok());
+    assert!(matches!(
+        data.check_scalar::<f64>(),
+        Err(PardisoError::ScalarTypeMismatch { .. })
+    ));
+
+    // releasing is allowed with any scalar type
+    data.phase = Phase::ReleaseAll;
+    assert!(data.check_scalar::<f64>().is_ok());
+}
+
+#[test]
+fn test_check_precision() {
+    use crate::*;
+    let mut data = PardisoData::default();
+
+    // no handle yet, so any precision is allowed
+    assert!(data.check_precision(Precision::Single).is_ok());
+
+    // analysis in double precision fixes the handle precision
+    data.phase = Phase::Analysis;
+    data.record_call(Precision::Double, 4);
+    assert!(data.check_precision(Precision::Double).is_ok());
+    assert!(matches!(
+        data.check_precision(Precision::Single),
+        Err(PardisoError::PrecisionMismatch {
+            expected: Precision::Double,
+            found: Precision::Single
+        })
+    ));
+
+    // releasing the handle resets it
+    data.phase = Phase::ReleaseAll;
+    data.record_call(Precision::Double, 4);
+    data.phase = Phase::Analysis;
+    assert!(data.check_precision(Precision::Single).is_ok());
+}
+
+#[test]
+fn test_iparm_common_roundtrip() {
+    use crate::*;
+    let options = IParm::read_common(&[0; 64], Ordering::Metis, Transpose::None)
+        .refinement_steps(5)
+        .pivot_perturbation(13)
+        .scaling(true)
+        .weighted_matching(true)
+        .pivoting(Pivoting::Diagonal)
+        .matrix_checker(true);
+
+    let mut iparm = [0; 64];
+    options.write_common(&mut iparm);
+    assert_eq!(iparm[7], 5);
+    assert_eq!(iparm[9], 13);
+    let read = IParm::read_common(&iparm, Ordering::Metis, Transpose::None);
+    assert_eq!(read, options);
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_iparm_options(ps: &mut dyn crate::PardisoInterface) {
+    use crate::*;
+    ps.pardisoinit().unwrap();
+
+    // nothing is reported before the analysis
+    assert_eq!(ps.get_stats().factor_nnz, None);
+    assert_eq!(ps.get_stats().factor_mflop, None);
+
+    let options = ps
+        .get_iparm_options()
+        .ordering(Ordering::Amd)
+        .transpose(Transpose::Transpose)
+        .scaling(true);
+    ps.set_iparm_options(&options).unwrap();
+    assert_eq!(ps.get_iparm_options(), options);
+    assert_eq!(ps.get_iparm(1), 0);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_iparm_options_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_iparm_options(&mut ps);
+
+    // MKL encodes the plain transpose as 2
+    assert_eq!(ps.get_iparm(11), 2);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_iparm_options_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_iparm_options(&mut ps);
+
+    // Panua encodes the plain transpose as 1
+    assert_eq!(ps.get_iparm(11), 1);
+
+    let options = ps.get_iparm_options().ordering(Ordering::ParallelMetis);
+    assert!(matches!(
+        ps.set_iparm_options(&options),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_dparm_options() {
+    use crate::*;
+    let mut solver = PanuaPardisoSolver::new().unwrap();
+    solver.set_solver(SolverType::Iterative);
+    solver.pardisoinit().unwrap();
+
+    let options = solver
+        .get_dparm_options()
+        .max_iterations(500)
+        .relative_tolerance(1e-8);
+    solver.set_dparm_options(&options);
+
+    assert_eq!(solver.get_dparm_options(), options);
+    assert_eq!(solver.get_dparm(0), 500.0);
+    assert_eq!(solver.get_dparm(1), 1e-8);
+}
+
+#[test]
+fn test_stats_display() {
+    use crate::*;
+    let stats = SolverStats {
+        phase: Phase::NumFact,
+        peak_analysis_memory_kb: Some(100),
+        permanent_memory_kb: Some(40),
+        factor_memory_kb: Some(80),
+        factor_nnz: Some(9),
+        factor_mflop: None,
+        perturbed_pivots: 0,
+        refinement_steps: 2,
+    };
+    assert_eq!(stats.peak_memory_kb(), Some(120));
+
+    let text = stats.to_string();
+    assert!(text.contains("Numerical Factorization"));
+    assert!(text.contains("factorization MFLOP:       n/a"));
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_ordering(ps: &mut dyn crate::PardisoInterface) {
+    use crate::*;
+    ps.pardisoinit().unwrap();
+
+    for ordering in [Ordering::Amd, Ordering::Metis, Ordering::User] {
+        ps.set_ordering(ordering).unwrap();
+        assert_eq!(ps.get_ordering(), ordering);
+        assert_eq!(ps.get_iparm_options().ordering, ordering);
+    }
+
+    // leaving user ordering turns off the user permutation
+    ps.set_ordering(Ordering::Metis).unwrap();
+    assert_eq!(ps.get_iparm(4), 0);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_ordering_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_ordering(&mut ps);
+    ps.set_ordering(Ordering::ParallelMetis).unwrap();
+    assert_eq!(ps.get_iparm(1), 3);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_ordering_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_ordering(&mut ps);
+    assert!(matches!(
+        ps.set_ordering(Ordering::ParallelMetis),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+}
+
+// without pardisoinit, iparm[0] = 0 and the backend defaults are filled
+// in before the entries set by the wrapper, e.g. the zero-based index
+// flag on MKL
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_default_iparm(ps: &mut dyn crate::PardisoInterface) {
+    use super::{TEST_A, TEST_IA, TEST_JA};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let ia: Vec<i32> = TEST_IA.iter().map(|i| i - 1).collect();
+    let ja: Vec<i32> = TEST_JA.iter().map(|j| j - 1).collect();
+    let matrix = CsrMatrix::new(4, &TEST_A, &ia, &ja).unwrap();
+    let mut b = vec![0.0, 1.0, 2.0, 3.0];
+    let mut x = vec![0.0; 4];
+
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    assert_eq!(ps.get_iparm(0), 0);
+    ps.set_phase(Phase::AnalysisNumFactSolveRefine);
+    ps.pardiso(&matrix, &mut b, &mut x, 1).unwrap();
+    assert_eq!(ps.get_iparm(0), 1);
+    assert_abs_diff_eq!(
+        x.as_slice(),
+        [16.0, 0.0, 5.0, -2.0].as_slice(),
+        epsilon = 1e-6
+    );
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_default_iparm_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_default_iparm(&mut ps);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_default_iparm_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_default_iparm(&mut ps);
+}
+
+// setting an option without pardisoinit fills in the other defaults
+// first, rather than leaving them zero
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_setter_defaults<P: crate::PardisoInterface>() {
+    use crate::*;
+
+    let mut reference = P::new().unwrap();
+    reference.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    reference.pardisoinit().unwrap();
+
+    let mut ps = P::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.set_ordering(Ordering::Amd).unwrap();
+    assert_eq!(ps.get_ordering(), Ordering::Amd);
+    assert_eq!(ps.get_iparm(0), 1);
+    for i in 2..64 {
+        assert_eq!(ps.get_iparm(i), reference.get_iparm(i), "iparm[{i}]");
+    }
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_setter_defaults_mkl() {
+    test_setter_defaults::<crate::MKLPardisoSolver>();
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_setter_defaults_panua() {
+    test_setter_defaults::<crate::PanuaPardisoSolver>();
+}
@@ -0,0 +1,61 @@
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_complex_symmetric(ps: &mut dyn crate::PardisoInterface) {
+    use super::{test_matrix, TEST_A};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    // The real symmetric test matrix from symmetric.rs scaled by (1 + i).
+    // The solutions are those of the real problem divided by (1 + i)
+    let n: i32 = 4;
+    let m: i32 = 3;
+    let scale = Complex64::new(1.0, 1.0);
+    let a: Vec<Complex64> = TEST_A.iter().map(|&v| scale * v).collect();
+    let matrix = test_matrix().with_values(&a).unwrap();
+
+    let mut b: Vec<Complex64> = (0..(n * m)).map(|x| Complex64::from(x as f64)).collect();
+    let mut x = vec![Complex64::default(); (n * m) as usize];
+
+    ps.set_matrix_type(MatrixType::ComplexSymmetric);
+    ps.pardisoinit().unwrap();
+
+    ps.set_phase(Phase::Analysis);
+    ps.pardiso_complex(&matrix, &mut [], &mut [], 1).unwrap();
+
+    ps.set_phase(Phase::NumFact);
+    ps.pardiso_complex(&matrix, &mut [], &mut [], 1).unwrap();
+
+    ps.set_phase(Phase::SolveIterativeRefine);
+    ps.pardiso_complex(&matrix, &mut b, &mut x, m).unwrap();
+
+    let xans = [
+        16.0, -0.0, 5.0, -2.0, 207.2, -5.6, 72.2, -19.6, 398.4, -11.2, 139.4, -37.2,
+    ];
+    for (xi, ai) in x.iter().zip(xans.iter()) {
+        let ai = ai / scale;
+        assert_abs_diff_eq!(xi.re, ai.re, epsilon = 1e-6);
+        assert_abs_diff_eq!(xi.im, ai.im, epsilon = 1e-6);
+    }
+
+    // real data is rejected for a complex matrix type
+    ps.set_phase(Phase::Analysis);
+    assert!(matches!(
+        ps.pardiso(&test_matrix(), &mut [], &mut [], 1),
+        Err(PardisoError::ScalarTypeMismatch { .. })
+    ));
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_complex_symmetric_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_complex_symmetric(&mut ps);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_complex_symmetric_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_complex_symmetric(&mut ps);
+}
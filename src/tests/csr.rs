@@ -0,0 +1,88 @@
+use super::{test_matrix, TEST_A, TEST_IA, TEST_JA};
+use crate::*;
+
+fn test_data() -> (Vec<f64>, Vec<i32>, Vec<i32>) {
+    (TEST_A.to_vec(), TEST_IA.to_vec(), TEST_JA.to_vec())
+}
+
+#[test]
+fn test_csr_valid() {
+    let (a, ia, ja) = test_data();
+    let m = test_matrix();
+    assert_eq!(m.n(), 4);
+    assert_eq!(m.nnz(), 9);
+    assert_eq!(m.base(), IndexBase::One);
+
+    // same matrix, zero-based
+    let ia0: Vec<i32> = ia.iter().map(|i| i - 1).collect();
+    let ja0: Vec<i32> = ja.iter().map(|j| j - 1).collect();
+    let m = CsrMatrix::new(4, &a, &ia0, &ja0).unwrap();
+    assert_eq!(m.base(), IndexBase::Zero);
+
+    // same matrix, 64-bit indices
+    let ia64: Vec<i64> = ia.iter().map(|&i| i as i64).collect();
+    let ja64: Vec<i64> = ja.iter().map(|&j| j as i64).collect();
+    let m = CsrMatrix::new(4, &a, &ia64, &ja64).unwrap();
+    assert_eq!(m.nnz(), 9);
+}
+
+#[test]
+fn test_csr_one_based_indices() {
+    let (a, ia, ja) = test_data();
+    let m = CsrMatrix::new(4, &a, &ia, &ja).unwrap();
+    assert!(m.one_based_indices().is_none());
+
+    let ia0: Vec<i32> = ia.iter().map(|i| i - 1).collect();
+    let ja0: Vec<i32> = ja.iter().map(|j| j - 1).collect();
+    let m = CsrMatrix::new(4, &a, &ia0, &ja0).unwrap();
+    let (ia1, ja1) = m.one_based_indices().unwrap();
+    assert_eq!((&ia1, &ja1), (&ia, &ja));
+
+    let m = m.with_indices(&ia1, &ja1);
+    assert_eq!(m.base(), IndexBase::One);
+    assert_eq!(m.values(), a.as_slice());
+}
+
+#[test]
+fn test_csr_bad_lengths() {
+    let (a, ia, ja) = test_data();
+
+    let r = CsrMatrix::new(4, &a, &ia[..4], &ja);
+    assert!(matches!(
+        r,
+        Err(PardisoError::RowPointerLength {
+            expected: 5,
+            found: 4
+        })
+    ));
+
+    let r = CsrMatrix::new(4, &a, &ia, &ja[..8]);
+    assert!(matches!(r, Err(PardisoError::ColumnIndexLength { .. })));
+
+    let r = CsrMatrix::new(4, &a[..8], &ia, &ja);
+    assert!(matches!(r, Err(PardisoError::ValuesLength { .. })));
+}
+
+#[test]
+fn test_csr_bad_structure() {
+    let (a, mut ia, mut ja) = test_data();
+
+    ja[4] = 5;
+    let r = CsrMatrix::new(4, &a, &ia, &ja);
+    assert!(matches!(
+        r,
+        Err(PardisoError::ColumnIndexOutOfRange { row: 1, index: 5 })
+    ));
+
+    ja[4] = 3;
+    ia[2] = 3;
+    let r = CsrMatrix::new(4, &a, &ia, &ja);
+    assert!(matches!(
+        r,
+        Err(PardisoError::RowPointerNotMonotone { row: 1 })
+    ));
+
+    let ia = vec![2, 5, 8, 10, 11];
+    let r = CsrMatrix::new(4, &a, &ia, &ja);
+    assert!(matches!(r, Err(PardisoError::InvalidIndexBase(2))));
+}
@@ -0,0 +1,129 @@
+#[test]
+fn test_factored_tracking() {
+    use crate::*;
+    let mut data = PardisoData::default();
+    assert!(matches!(
+        data.check_factored(),
+        Err(PardisoError::NotFactorized { mnum: 1 })
+    ));
+
+    data.phase = Phase::AnalysisNumFact;
+    data.record_call(Precision::Double, 4);
+    assert!(data.check_factored().is_ok());
+
+    // solving keeps the factorization, a new analysis discards it
+    data.phase = Phase::SolveIterativeRefine;
+    data.record_call(Precision::Double, 4);
+    assert!(data.check_factored().is_ok());
+    data.phase = Phase::Analysis;
+    data.record_call(Precision::Double, 4);
+    assert!(data.check_factored().is_err());
+
+    // releasing one matrix number leaves the others
+    data.phase = Phase::NumFact;
+    data.record_call(Precision::Double, 4);
+    data.mnum = 2;
+    data.record_call(Precision::Double, 4);
+    data.phase = Phase::ReleaseLUandMNUM;
+    data.record_call(Precision::Double, 4);
+    assert!(data.check_factored().is_err());
+    data.mnum = 1;
+    assert!(data.check_factored().is_ok());
+}
+
+// factorizes the 4x4 symmetric test matrix, with det(A) = -5
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn factorize(ps: &mut dyn crate::PardisoInterface) {
+    use super::test_matrix;
+    use crate::*;
+    let matrix = test_matrix();
+
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+
+    ps.set_phase(Phase::Analysis);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+    assert!(matches!(
+        ps.log_determinant(),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+    assert!(matches!(
+        ps.get_inertia(),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+
+    ps.set_phase(Phase::NumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+    assert_eq!(ps.get_inertia().unwrap().negative, 1);
+
+    // inertia is undefined for nonsymmetric types
+    ps.set_matrix_type(MatrixType::RealNonsymmetric);
+    assert!(matches!(
+        ps.get_inertia(),
+        Err(PardisoError::InertiaUndefined(_))
+    ));
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_log_determinant_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    factorize(&mut ps);
+    assert!(matches!(
+        ps.log_determinant(),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_log_determinant_panua() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    factorize(&mut ps);
+
+    let (logdet, sign) = ps.log_determinant().unwrap();
+    assert_abs_diff_eq!(logdet, 5f64.ln(), epsilon = 1e-10);
+    assert_eq!(sign, Sign::Negative);
+
+    // the determinant is computed even if the user turned it off, and
+    // the setting is kept.  det(2A) = 16 det(A)
+    let a: Vec<f64> = super::TEST_A.iter().map(|v| 2.0 * v).collect();
+    let matrix = super::test_matrix().with_values(&a).unwrap();
+    ps.set_iparm(32, 0);
+    ps.set_phase(Phase::NumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+    assert_eq!(ps.get_iparm(32), 0);
+
+    // later calls and iparm changes do not affect the result
+    ps.set_iparm(22, 0);
+    ps.set_dparm(32, 0.0);
+    let (logdet, sign) = ps.log_determinant().unwrap();
+    assert_abs_diff_eq!(logdet, 80f64.ln(), epsilon = 1e-10);
+    assert_eq!(sign, Sign::Negative);
+}
+
+#[test]
+fn test_inertia() {
+    use crate::*;
+    let mut data = PardisoData::default();
+    data.iparm[21] = 2;
+    data.iparm[22] = 1;
+    data.iparm[13] = 1;
+    data.phase = Phase::NumFact;
+    data.record_call(Precision::Double, 4);
+
+    let inertia = data.factored[&1];
+    assert_eq!(inertia.zero, 1);
+    assert!(inertia.is_singular());
+    assert_eq!(inertia.perturbed_pivots, 1);
+
+    // positive definite types have only positive eigenvalues
+    data.mtype = MatrixType::RealSymmetricPositiveDefinite;
+    data.record_call(Precision::Double, 4);
+    assert_eq!(data.factored[&1].positive, 4);
+    assert!(!data.factored[&1].is_singular());
+}
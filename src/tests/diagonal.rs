@@ -0,0 +1,41 @@
+#[cfg(feature = "mkl")]
+#[test]
+fn test_diagonals_mkl() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let matrix = super::test_matrix();
+
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    assert!(matches!(
+        ps.factor_diagonal::<f64>(),
+        Err(PardisoError::NotFactorized { mnum: 1 })
+    ));
+
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+
+    // the diagonals are only kept on request
+    assert!(matches!(
+        ps.factor_diagonal::<f64>(),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+    assert!(matches!(
+        ps.original_diagonal::<f64>(),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+    ps.set_keep_diagonal(true);
+    ps.set_phase(Phase::NumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+
+    let da = ps.original_diagonal::<f64>().unwrap();
+    assert_abs_diff_eq!(da.as_slice(), [1.0, 5.0, 4.0, 5.0].as_slice());
+
+    let df = ps.factor_diagonal::<f64>().unwrap();
+    assert_eq!(df.len(), 4);
+    assert!(df.iter().all(|d| d.is_finite()));
+
+    assert!(ps.factor_diagonal::<Complex64>().is_err());
+}
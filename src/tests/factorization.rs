@@ -0,0 +1,243 @@
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_factorization<P: crate::PardisoInterface>() {
+    use super::{test_matrix, TEST_A};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let n = 4;
+    let (a, matrix) = (TEST_A, test_matrix());
+
+    let b: Vec<f64> = (0..(n * 3)).map(|x| x as f64).collect();
+
+    let solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    let analyzed = solver.analyze(&matrix).unwrap();
+    let mut factored = analyzed.factorize(&a).unwrap();
+
+    let x = factored.solve(&b).unwrap();
+    let xans = vec![
+        16.0, -0.0, 5.0, -2.0, 207.2, -5.6, 72.2, -19.6, 398.4, -11.2, 139.4, -37.2,
+    ];
+    assert_abs_diff_eq!(x.as_slice(), xans.as_slice(), epsilon = 1e-6);
+
+    // rhs length must be a multiple of n
+    assert!(matches!(
+        factored.solve(&b[..5]),
+        Err(PardisoError::RhsNotMultiple { n: 4, found: 5 })
+    ));
+
+    // refactor with scaled values on the same pattern
+    let a2: Vec<f64> = a.iter().map(|v| 2.0 * v).collect();
+    let mut factored = factored.into_analyzed().factorize(&a2).unwrap();
+    let x2 = factored.solve(&b).unwrap();
+    let xans2: Vec<f64> = xans.iter().map(|v| 0.5 * v).collect();
+    assert_abs_diff_eq!(x2.as_slice(), xans2.as_slice(), epsilon = 1e-6);
+
+    // values must match the analyzed pattern
+    let analyzed = factored.into_analyzed();
+    assert!(analyzed.factorize(&a2[..8]).is_err());
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_factorization_f32<P: crate::PardisoInterface>() {
+    use super::{test_matrix, TEST_A};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let n = 4;
+    let a: Vec<f32> = TEST_A.iter().map(|&v| v as f32).collect();
+    let matrix = test_matrix().with_values(&a).unwrap();
+
+    let b: Vec<f32> = (0..n).map(|x| x as f32).collect();
+
+    let solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    let mut factored = solver.analyze(&matrix).unwrap().factorize(&a).unwrap();
+    let x = factored.solve(&b).unwrap();
+
+    let xans: Vec<f32> = vec![16.0, -0.0, 5.0, -2.0];
+    assert_abs_diff_eq!(x.as_slice(), xans.as_slice(), epsilon = 1e-3);
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_transpose<P: crate::PardisoInterface>() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    // nonsymmetric 3x3 matrix
+    // [ 2  1  0
+    //   0  3  1
+    //   1  0  4 ]
+    let n = 3;
+    let a = vec![2.0, 1.0, 3.0, 1.0, 1.0, 4.0];
+    let ia = vec![0, 2, 4, 6];
+    let ja = vec![0, 1, 1, 2, 0, 2];
+    let matrix = CsrMatrix::new(n, &a, &ia, &ja).unwrap();
+    let b = vec![1.0, 2.0, 3.0];
+
+    let solver = Solver::<P>::new(MatrixType::RealNonsymmetric).unwrap();
+    let mut factored = solver.analyze(&matrix).unwrap().factorize(&a).unwrap();
+    let transpose = factored.interface().get_iparm(11);
+
+    let x = factored.solve(&b).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), [0.28, 0.44, 0.68].as_slice(), epsilon = 1e-10);
+    let x = factored.solve_transpose(&b).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), [0.2, 0.6, 0.6].as_slice(), epsilon = 1e-10);
+    assert_eq!(factored.interface().get_iparm(11), transpose);
+
+    // same matrix scaled by 1 + i, so that A^T and A^H differ
+    let s = Complex64::new(1.0, 1.0);
+    let ac: Vec<Complex64> = a.iter().map(|&v| s * v).collect();
+    let bc: Vec<Complex64> = b.iter().map(|&v| Complex64::from(v)).collect();
+    let matrix = CsrMatrix::new(n, &ac, &ia, &ja).unwrap();
+
+    let solver = Solver::<P>::new(MatrixType::ComplexNonsymmetric).unwrap();
+    let mut factored = solver.analyze(&matrix).unwrap().factorize(&ac).unwrap();
+
+    let xt = factored.solve_transpose(&bc).unwrap();
+    let xh = factored.solve_conjugate_transpose(&bc).unwrap();
+    for (i, v) in [0.2, 0.6, 0.6].into_iter().enumerate() {
+        assert!((xt[i] - v / s).norm() < 1e-10);
+        assert!((xh[i] - v / s.conj()).norm() < 1e-10);
+    }
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_factorization_mkl() {
+    test_factorization::<crate::MKLPardisoSolver>();
+    test_factorization_f32::<crate::MKLPardisoSolver>();
+    test_transpose::<crate::MKLPardisoSolver>();
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_factorization_panua() {
+    test_factorization::<crate::PanuaPardisoSolver>();
+    test_factorization_f32::<crate::PanuaPardisoSolver>();
+    test_transpose::<crate::PanuaPardisoSolver>();
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_partial_solves<P: crate::PardisoInterface>() {
+    use super::{test_matrix, TEST_A};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let n = 4;
+    let (a, matrix) = (TEST_A, test_matrix());
+    let b: Vec<f64> = (0..n).map(|x| x as f64).collect();
+
+    let mut solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    solver.interface_mut().set_iparm(7, 0);
+    let mut factored = solver.analyze(&matrix).unwrap().factorize(&a).unwrap();
+
+    // forward, diagonal and backward steps compose to a full solve
+    let y = factored.solve_forward(&b).unwrap();
+    let z = factored.solve_diagonal(&y).unwrap();
+    let x = factored.solve_backward(&z).unwrap();
+    let xans = factored.solve(&b).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), xans.as_slice(), epsilon = 1e-8);
+
+    // iterative refinement only applies to the full solve
+    let mut solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    solver.interface_mut().set_iparm(7, 2);
+    let mut factored = solver.analyze(&matrix).unwrap().factorize(&a).unwrap();
+    let y = factored.solve_forward(&b).unwrap();
+    let z = factored.solve_diagonal(&y).unwrap();
+    let x = factored.solve_backward(&z).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), xans.as_slice(), epsilon = 1e-8);
+
+    // the diagonal step needs a symmetric or Hermitian type
+    let solver = Solver::<P>::new(MatrixType::RealNonsymmetric).unwrap();
+    let full = CsrMatrix::new(n, &[1.0, 2.0, 3.0, 4.0], &[1, 2, 3, 4, 5], &[1, 2, 3, 4]).unwrap();
+    let mut factored = solver
+        .analyze(&full)
+        .unwrap()
+        .factorize(full.values())
+        .unwrap();
+    assert!(matches!(
+        factored.solve_diagonal(&b),
+        Err(PardisoError::PartialSolveUnavailable(_))
+    ));
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_partial_solves_mkl() {
+    test_partial_solves::<crate::MKLPardisoSolver>();
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_partial_solves_panua() {
+    test_partial_solves::<crate::PanuaPardisoSolver>();
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_factorization_set<P: crate::PardisoInterface>() {
+    use super::{test_matrix, TEST_A};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let n = 4;
+    let (a, matrix) = (TEST_A, test_matrix());
+    let a2: Vec<f64> = a.iter().map(|v| 2.0 * v).collect();
+    let a4: Vec<f64> = a.iter().map(|v| 4.0 * v).collect();
+    let b: Vec<f64> = (0..n).map(|x| x as f64).collect();
+    let xans = [16.0, -0.0, 5.0, -2.0];
+
+    let solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    let mut set = solver.analyze_set(&matrix, 2).unwrap();
+    let h1 = set.factorize(&a).unwrap();
+    let h2 = set.factorize(&a2).unwrap();
+    assert_eq!(set.len(), 2);
+    assert!(matches!(
+        set.factorize(&a4),
+        Err(PardisoError::FactorizationSetFull { maxfct: 2 })
+    ));
+
+    // solve against either factorization
+    let x2 = set.solve(h2, &b).unwrap();
+    let x1 = set.solve(h1, &b).unwrap();
+    assert_abs_diff_eq!(x1.as_slice(), xans.as_slice(), epsilon = 1e-8);
+    let xans2: Vec<f64> = xans.iter().map(|v| 0.5 * v).collect();
+    assert_abs_diff_eq!(x2.as_slice(), xans2.as_slice(), epsilon = 1e-8);
+
+    // a released slot is reused, and the old handle is stale
+    set.release(h1).unwrap();
+    let h4 = set.factorize(&a4).unwrap();
+    assert_eq!(h4.mnum(), h1.mnum());
+    assert!(matches!(
+        set.solve(h1, &b),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+    let x4 = set.solve(h4, &b).unwrap();
+    let xans4: Vec<f64> = xans.iter().map(|v| 0.25 * v).collect();
+    assert_abs_diff_eq!(x4.as_slice(), xans4.as_slice(), epsilon = 1e-8);
+
+    // handles belong to the set that created them
+    let solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    let mut other = solver.analyze_set(&matrix, 2).unwrap();
+    other.factorize(&a).unwrap();
+    assert!(matches!(
+        other.solve(h4, &b),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+
+    let solver = Solver::<P>::new(MatrixType::RealSymmetricIndefinite).unwrap();
+    assert!(matches!(
+        solver.analyze_set(&matrix, 0),
+        Err(PardisoError::InvalidMaxfct(0))
+    ));
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_factorization_set_mkl() {
+    test_factorization_set::<crate::MKLPardisoSolver>();
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_factorization_set_panua() {
+    test_factorization_set::<crate::PanuaPardisoSolver>();
+}
@@ -0,0 +1,63 @@
+#[cfg(feature = "mkl")]
+#[test]
+fn test_save_restore_handle_mkl() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let matrix = super::test_matrix();
+    let dir = std::env::temp_dir().join(format!("pardiso_handle_{}", std::process::id()));
+
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+    ps.save_handle(&dir).unwrap();
+    let iparm = *ps.get_iparms();
+    drop(ps);
+
+    // resume without analysis or factorization
+    let mut ps = MKLPardisoSolver::restore_handle(&dir).unwrap();
+    assert_eq!(ps.get_iparms(), &iparm);
+    assert_eq!(ps.get_inertia().unwrap().negative, 1);
+
+    let mut b = vec![0.0, 1.0, 2.0, 3.0];
+    let mut x = vec![0.0; 4];
+    ps.set_phase(Phase::SolveIterativeRefine);
+    ps.pardiso(&matrix, &mut b, &mut x, 1).unwrap();
+    assert_abs_diff_eq!(
+        x.as_slice(),
+        [16.0, 0.0, 5.0, -2.0].as_slice(),
+        epsilon = 1e-6
+    );
+
+    MKLPardisoSolver::delete_handle(&dir).unwrap();
+    assert!(MKLPardisoSolver::restore_handle(&dir).is_err());
+    let _ = std::fs::remove_dir(&dir);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_read_state() {
+    use crate::mkl::handle::{read_state, write_state, STATE_FILE};
+    use crate::*;
+
+    let dir = std::env::temp_dir().join(format!("pardiso_state_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let data = PardisoData {
+        n: 4,
+        ..Default::default()
+    };
+    write_state(&data, &dir).unwrap();
+    assert_eq!(read_state(&dir).unwrap().n, 4);
+
+    // negative dimensions are rejected
+    let state = std::fs::read_to_string(dir.join(STATE_FILE)).unwrap();
+    std::fs::write(dir.join(STATE_FILE), state.replace("n 4", "n -1")).unwrap();
+    assert!(matches!(
+        read_state(&dir),
+        Err(PardisoError::InvalidSavedState(_))
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -1,3 +1,22 @@
+use crate::CsrMatrix;
+
+// The 4x4 symmetric indefinite test matrix
+//
+// [ 1  0 -2  3
+//   0  5  1  2
+//  -2  1  4 -7
+//   3  2 -7  5 ]
+//
+// stored as its upper triangle in 1-based CSR format.  det(A) = -5,
+// the inertia is (3, 1, 0) and A x = [0, 1, 2, 3] for x = [16, 0, 5, -2]
+pub(crate) static TEST_A: [f64; 9] = [1.0, -2.0, 3.0, 5.0, 1.0, 2.0, 4.0, -7.0, 5.0];
+pub(crate) static TEST_IA: [i32; 5] = [1, 4, 7, 9, 10];
+pub(crate) static TEST_JA: [i32; 9] = [1, 3, 4, 2, 3, 4, 3, 4, 4];
+
+pub(crate) fn test_matrix() -> CsrMatrix<'static> {
+    CsrMatrix::new(4, &TEST_A, &TEST_IA, &TEST_JA).unwrap()
+}
+
 #[cfg(test)]
 mod libloading;
 
@@ -9,3 +28,39 @@ mod pardisoinit;
 
 #[cfg(test)]
 mod api;
+
+#[cfg(test)]
+mod csr;
+
+#[cfg(test)]
+mod factorization;
+
+#[cfg(test)]
+mod complex;
+
+#[cfg(test)]
+mod permutation;
+
+#[cfg(test)]
+mod schur;
+
+#[cfg(test)]
+mod selinv;
+
+#[cfg(test)]
+mod determinant;
+
+#[cfg(test)]
+mod sparse;
+
+#[cfg(test)]
+mod ooc;
+
+#[cfg(test)]
+mod handle;
+
+#[cfg(test)]
+mod diagonal;
+
+#[cfg(test)]
+mod pivot;
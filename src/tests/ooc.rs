@@ -0,0 +1,71 @@
+#[cfg(feature = "mkl")]
+#[test]
+fn test_out_of_core_mkl() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let matrix = super::test_matrix();
+    let mut b = vec![0.0, 1.0, 2.0, 3.0];
+    let mut x = vec![0.0; 4];
+
+    let directory = std::env::temp_dir();
+    let config = OutOfCoreConfig::default()
+        .mode(OutOfCoreMode::Always)
+        .directory(&directory);
+
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    ps.set_out_of_core(&config).unwrap();
+    assert_eq!(ps.get_out_of_core(), Some(&config));
+    assert_eq!(ps.get_iparm(59), 2);
+
+    let prefix = ps.get_out_of_core_prefix().unwrap();
+    assert!(prefix.starts_with(directory.to_str().unwrap()));
+
+    ps.set_phase(Phase::AnalysisNumFactSolveRefine);
+    ps.pardiso(&matrix, &mut b, &mut x, 1).unwrap();
+    assert_abs_diff_eq!(
+        x.as_slice(),
+        [16.0, 0.0, 5.0, -2.0].as_slice(),
+        epsilon = 1e-6
+    );
+
+    // scratch files are removed on drop
+    drop(ps);
+    let name = std::path::Path::new(&prefix).file_name().unwrap();
+    let remaining = std::fs::read_dir(&directory)
+        .unwrap()
+        .flatten()
+        .filter(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .starts_with(&*name.to_string_lossy())
+        })
+        .count();
+    assert_eq!(remaining, 0);
+
+    // the scratch directory must exist
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    let config = config.directory(directory.join("no_such_directory"));
+    assert!(ps.set_out_of_core(&config).is_err());
+}
+
+// the prefix of one solver must not match the files of another, e.g.
+// solver 1 and solver 10 of the same process
+#[cfg(feature = "mkl")]
+#[test]
+fn test_remove_ooc_files() {
+    use crate::mkl::interface::remove_ooc_files;
+
+    let directory = std::env::temp_dir().join(format!("ooc_test_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    for name in ["pardiso_ooc_0_1_.fct", "pardiso_ooc_0_10_.fct"] {
+        std::fs::write(directory.join(name), b"").unwrap();
+    }
+
+    remove_ooc_files(&directory.join("pardiso_ooc_0_1_"));
+    assert!(!directory.join("pardiso_ooc_0_1_.fct").exists());
+    assert!(directory.join("pardiso_ooc_0_10_.fct").exists());
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,100 @@
+#[test]
+fn test_permutation_validation() {
+    use crate::*;
+
+    let p = Permutation::new(vec![2, 0, 3, 1], IndexBase::Zero).unwrap();
+    assert_eq!(p.len(), 4);
+    assert_eq!(p.to_base(IndexBase::One).as_slice(), &[3, 1, 4, 2]);
+
+    // duplicate entry
+    let r = Permutation::new(vec![2, 0, 2, 1], IndexBase::Zero);
+    assert!(matches!(
+        r,
+        Err(PardisoError::InvalidPermutation { index: 2, value: 2 })
+    ));
+
+    // zero is out of range for a one-based permutation
+    let r = Permutation::new(vec![2, 0, 3, 1], IndexBase::One);
+    assert!(matches!(
+        r,
+        Err(PardisoError::InvalidPermutation { index: 1, value: 0 })
+    ));
+}
+
+#[test]
+fn test_prepare_perm() {
+    use crate::*;
+    let mut data = PardisoData::default();
+
+    // a user permutation must match the matrix dimension, and is
+    // shifted to the base of the matrix
+    data.iparm[4] = 1;
+    data.perm = vec![3, 1, 4, 2];
+    data.perm_base = IndexBase::One;
+    assert!(matches!(
+        data.prepare_perm(5, IndexBase::One),
+        Err(PardisoError::PermutationLength {
+            expected: 5,
+            found: 4
+        })
+    ));
+    data.prepare_perm(4, IndexBase::Zero).unwrap();
+    assert_eq!(data.perm, vec![2, 0, 3, 1]);
+
+    // a returned permutation gets a buffer of length n
+    data.iparm[4] = 2;
+    data.perm.clear();
+    data.prepare_perm(4, IndexBase::One).unwrap();
+    assert_eq!(data.perm.len(), 4);
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_permutation_reuse<P: crate::PardisoInterface>() {
+    use super::{test_matrix, TEST_A};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let n = 4;
+    let matrix = test_matrix();
+
+    // compute an ordering and read it back
+    let mut ps = P::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    ps.set_return_perm(true).unwrap();
+    ps.set_phase(Phase::Analysis);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+    let perm = ps.get_permutation().unwrap();
+    assert_eq!(perm.len(), n);
+
+    // reuse the ordering in a new solver
+    let mut ps = P::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    ps.set_perm(perm).unwrap();
+    assert_eq!(ps.get_ordering(), Ordering::User);
+
+    let mut factored = Solver::from_interface(ps)
+        .analyze(&matrix)
+        .unwrap()
+        .factorize(&TEST_A)
+        .unwrap();
+    let x = factored.solve(&[0.0, 1.0, 2.0, 3.0]).unwrap();
+    assert_abs_diff_eq!(
+        x.as_slice(),
+        [16.0, -0.0, 5.0, -2.0].as_slice(),
+        epsilon = 1e-6
+    );
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_permutation_reuse_mkl() {
+    test_permutation_reuse::<crate::MKLPardisoSolver>();
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_permutation_reuse_panua() {
+    test_permutation_reuse::<crate::PanuaPardisoSolver>();
+}
@@ -0,0 +1,45 @@
+#[cfg(feature = "mkl-pivot-hook")]
+#[test]
+fn test_default_pivot() {
+    use crate::mkl::pivot::default_pivot;
+
+    assert_eq!(default_pivot(1.0, 1e-8), None);
+    assert_eq!(default_pivot(-1.0, 1e-8), None);
+    assert_eq!(default_pivot(1e-12, 1e-8), Some(1e-8));
+    assert_eq!(default_pivot(-1e-12, 1e-8), Some(-1e-8));
+    assert_eq!(default_pivot(0.0, 1e-8), Some(-1e-8));
+}
+
+// the ownership rules behind set_pivot_handler, tested without a
+// solver so that MKL is not needed
+#[cfg(feature = "mkl-pivot-hook")]
+#[test]
+fn test_pivot_handler_guard() {
+    use crate::mkl::pivot::{self, PivotHandler};
+    use crate::*;
+    use std::sync::Arc;
+
+    // ids that no solver will have
+    let (id1, id2) = (usize::MAX - 1, usize::MAX);
+    let regularize = || -> Arc<PivotHandler> {
+        Arc::new(|_aii: f64, bii: f64, eps: f64| (bii.abs() < eps).then_some(eps))
+    };
+    pivot::install(id1, regularize()).unwrap();
+    assert_eq!(pivot::owner(), Some(id1));
+
+    // the owner may replace its handler, other solvers may not
+    pivot::install(id1, regularize()).unwrap();
+    assert!(matches!(
+        pivot::install(id2, regularize()),
+        Err(PardisoError::PivotHandlerInUse)
+    ));
+    pivot::clear(id2);
+    assert_eq!(pivot::owner(), Some(id1));
+
+    // clearing by the owner releases the handler
+    pivot::clear(id1);
+    pivot::install(id2, regularize()).unwrap();
+    assert_eq!(pivot::owner(), Some(id2));
+    pivot::clear(id2);
+    assert_eq!(pivot::owner(), None);
+}
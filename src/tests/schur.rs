@@ -0,0 +1,84 @@
+#[test]
+fn test_schur_block() {
+    use crate::dense::schur_block;
+    use crate::*;
+
+    assert_eq!(schur_block(4, &[3, 1]).unwrap(), vec![1, 3]);
+
+    // repeated, out of range and empty blocks
+    for block in [&[1, 1][..], &[2, 4], &[]] {
+        assert!(matches!(
+            schur_block(4, block),
+            Err(PardisoError::InvalidSchurBlock)
+        ));
+    }
+}
+
+// complement of the trailing 2x2 block, A22 - A21 inv(A11) A12
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn check_schur(s: &crate::DenseMatrix) {
+    use approx::assert_abs_diff_eq;
+    let sans = [-0.2, -1.4, -1.4, -4.8];
+    assert_eq!((s.nrows(), s.ncols()), (2, 2));
+    assert_abs_diff_eq!(s.as_slice(), sans.as_slice(), epsilon = 1e-10);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_schur_mkl() {
+    use crate::*;
+    let matrix = super::test_matrix();
+
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    let s = ps.schur_complement(&matrix, &[3, 2]).unwrap();
+    check_schur(&s);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_schur_panua() {
+    use crate::*;
+    let matrix = super::test_matrix();
+
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    let s = ps.schur_complement(&matrix, &[3, 2]).unwrap();
+    check_schur(&s);
+
+    // only trailing blocks are supported
+    assert!(matches!(
+        ps.schur_complement(&matrix, &[0, 3]),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+}
+
+// nonsymmetric matrices give a nonsymmetric complement, returned in
+// row-major order
+#[cfg(feature = "mkl")]
+#[test]
+fn test_schur_nonsymmetric_mkl() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    // [[4, 1, 0], [2, 5, 1], [0, 3, 6]]
+    let a = [4.0, 1.0, 2.0, 5.0, 1.0, 3.0, 6.0];
+    let ia = [1, 3, 6, 8];
+    let ja = [1, 2, 1, 2, 3, 2, 3];
+    let matrix = CsrMatrix::new(3, &a, &ia, &ja).unwrap();
+
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealNonsymmetric);
+    ps.pardisoinit().unwrap();
+    let s = ps.schur_complement(&matrix, &[1, 2]).unwrap();
+    let sans = [4.5, 1.0, 3.0, 6.0];
+    assert_abs_diff_eq!(s.as_slice(), sans.as_slice(), epsilon = 1e-10);
+
+    // the partial factorization cannot be used to solve
+    assert!(matches!(
+        ps.solve_sparse(&matrix, &[0], &[1.0], &[0]),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+}
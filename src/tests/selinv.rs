@@ -0,0 +1,52 @@
+#[cfg(feature = "panua")]
+#[test]
+fn test_selected_inverse_panua() {
+    use super::{TEST_A, TEST_IA, TEST_JA};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    // the test matrix, 0-based
+    let ia: Vec<i32> = TEST_IA.iter().map(|i| i - 1).collect();
+    let ja: Vec<i32> = TEST_JA.iter().map(|j| j - 1).collect();
+    let matrix = CsrMatrix::new(4, &TEST_A, &ia, &ja).unwrap();
+
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    assert!(matches!(
+        ps.selected_inverse(&matrix),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+
+    // the values array must not be overwritten through pardiso()
+    ps.set_phase(Phase::SelectedInversion);
+    assert!(matches!(
+        ps.pardiso(&matrix, &mut [], &mut [], 1),
+        Err(PardisoError::UnsupportedOption { .. })
+    ));
+
+    let inverse = ps.selected_inverse(&matrix).unwrap();
+    let ans = [38.8, 13.8, -3.4, 0.2, -0.4, 0.2, 4.8, -1.4, 0.2];
+    assert_abs_diff_eq!(inverse.values(), ans.as_slice(), epsilon = 1e-8);
+    assert_eq!(inverse.matrix().base(), IndexBase::Zero);
+
+    let diag = ps.diag_of_inverse(&matrix).unwrap();
+    assert_abs_diff_eq!(
+        diag.as_slice(),
+        [38.8, 0.2, 4.8, 0.2].as_slice(),
+        epsilon = 1e-8
+    );
+
+    // the factorization is still usable
+    let mut b = vec![1.0, 0.0, 0.0, 0.0];
+    let mut x = vec![0.0; 4];
+    ps.set_phase(Phase::SolveIterativeRefine);
+    ps.pardiso(&matrix, &mut b, &mut x, 1).unwrap();
+    assert_abs_diff_eq!(
+        x.as_slice(),
+        [38.8, -1.4, 13.8, -3.4].as_slice(),
+        epsilon = 1e-8
+    );
+}
@@ -0,0 +1,89 @@
+#[test]
+fn test_scatter() {
+    use crate::dense::scatter;
+    use crate::*;
+
+    let b = scatter(4, &[3, 1], &[2.0, -1.0]).unwrap();
+    assert_eq!(b, vec![0.0, -1.0, 0.0, 2.0]);
+
+    assert!(matches!(
+        scatter(4, &[3, 1], &[2.0]),
+        Err(PardisoError::RhsLength {
+            expected: 2,
+            found: 1
+        })
+    ));
+    assert!(matches!(
+        scatter(4, &[1, 1], &[2.0, -1.0]),
+        Err(PardisoError::InvalidSparseIndex { index: 1 })
+    ));
+    assert!(matches!(
+        scatter(4, &[4], &[2.0]),
+        Err(PardisoError::InvalidSparseIndex { index: 4 })
+    ));
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_solve_sparse(ps: &mut dyn crate::PardisoInterface) {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    let matrix = super::test_matrix();
+
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    assert!(matches!(
+        ps.solve_sparse(&matrix, &[0], &[1.0], &[0, 2]),
+        Err(PardisoError::NotFactorized { .. })
+    ));
+
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+
+    // entries of the first column of the inverse
+    let x = ps.solve_sparse(&matrix, &[0], &[1.0], &[0, 2]).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), [38.8, 13.8].as_slice(), epsilon = 1e-8);
+
+    // and of the second, scaled
+    let x = ps.solve_sparse(&matrix, &[1], &[5.0], &[3, 1]).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), [1.0, 1.0].as_slice(), epsilon = 1e-8);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_solve_sparse_mkl() {
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_solve_sparse(&mut ps);
+
+    // solves without a mask from the analysis are dense and leave the
+    // factorization usable
+    assert!(ps.get_inertia().is_ok());
+
+    // analyze with a mask for partial solves
+    let matrix = super::test_matrix();
+    ps.set_sparse_mask(4, &[0, 2]).unwrap();
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+    let x = ps.solve_sparse(&matrix, &[0], &[1.0], &[2, 0]).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), [13.8, 38.8].as_slice(), epsilon = 1e-8);
+
+    // patterns outside of the mask fall back to a dense solve
+    let x = ps.solve_sparse(&matrix, &[1], &[5.0], &[3, 1]).unwrap();
+    assert_abs_diff_eq!(x.as_slice(), [1.0, 1.0].as_slice(), epsilon = 1e-8);
+    assert!(ps.get_inertia().is_ok());
+
+    assert!(matches!(
+        ps.set_sparse_mask(4, &[4]),
+        Err(PardisoError::InvalidSparseIndex { index: 4 })
+    ));
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_solve_sparse_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_solve_sparse(&mut ps);
+}
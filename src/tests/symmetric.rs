@@ -1,5 +1,6 @@
 #[cfg(any(feature = "mkl", feature = "panua"))]
-fn test_symmetric(ps: &mut dyn crate::PardisoInterface) {
+fn test_symmetric(ps: &mut dyn crate::PardisoInterface, base: crate::IndexBase) {
+    use super::{TEST_A, TEST_IA, TEST_JA};
     use crate::*;
     use approx::assert_abs_diff_eq;
 
@@ -13,10 +14,12 @@ fn test_symmetric(ps: &mut dyn crate::PardisoInterface) {
     //  -2  1  4 -7
     //   3  2 -7  5 ]
 
-    // triangular matrix data (1-based indexing, CSR format)
-    let a = vec![1.0, -2.0, 3.0, 5.0, 1.0, 2.0, 4.0, -7.0, 5.0];
-    let ia = vec![1, 4, 7, 9, 10];
-    let ja = vec![1, 3, 4, 2, 3, 4, 3, 4, 4];
+    // triangular matrix data (CSR format), shifted to the requested
+    // base.  The solver picks it up from ia[0]
+    let shift = 1 - base as i32;
+    let ia: Vec<i32> = TEST_IA.iter().map(|i| i - shift).collect();
+    let ja: Vec<i32> = TEST_JA.iter().map(|j| j - shift).collect();
+    let matrix = CsrMatrix::new(n as usize, &TEST_A, &ia, &ja).unwrap();
 
     // Generate some right hand side data
     let mut b: Vec<f64> = (0..(n * m)).map(|x| x as f64).collect();
@@ -30,18 +33,18 @@ fn test_symmetric(ps: &mut dyn crate::PardisoInterface) {
 
     // compute the symbolic factorization
     ps.set_phase(Phase::Analysis);
-    ps.pardiso(&a, &ia, &ja, &mut [], &mut [], n, 1).unwrap();
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
 
     // compute the numeric factorization
     ps.set_phase(Phase::NumFact);
-    ps.pardiso(&a, &ia, &ja, &mut b, &mut x, n, m).unwrap();
+    ps.pardiso(&matrix, &mut b, &mut x, m).unwrap();
 
     assert!(ps.get_num_positive_eigenvalues() == 3);
     assert!(ps.get_num_negative_eigenvalues() == 1);
 
     // compute the solutions
     ps.set_phase(Phase::SolveIterativeRefine);
-    ps.pardiso(&a, &ia, &ja, &mut b, &mut x, n, m).unwrap();
+    ps.pardiso(&matrix, &mut b, &mut x, m).unwrap();
 
     let xans = vec![
         16.0, -0.0, 5.0, -2.0, 207.2, -5.6, 72.2, -19.6, 398.4, -11.2, 139.4, -37.2,
@@ -53,7 +56,7 @@ fn test_symmetric(ps: &mut dyn crate::PardisoInterface) {
     // Free solver resources
     // Unnecessary (but harmless) since Drop impl does this for you
     ps.set_phase(Phase::ReleaseAll);
-    ps.pardiso(&a, &ia, &ja, &mut b, &mut x, n, m).unwrap();
+    ps.pardiso(&matrix, &mut b, &mut x, m).unwrap();
 }
 
 #[cfg(feature = "mkl")]
@@ -62,7 +65,15 @@ fn test_symmetric_mkl() {
     use crate::*;
     // Create an MKL Pardiso solver instance
     let mut ps = MKLPardisoSolver::new().unwrap();
-    test_symmetric(&mut ps);
+    test_symmetric(&mut ps, IndexBase::One);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_symmetric_mkl_zero_based() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_symmetric(&mut ps, IndexBase::Zero);
 }
 
 #[cfg(feature = "panua")]
@@ -71,5 +82,136 @@ fn test_symmetric_panua() {
     use crate::*;
     // Create a Panua Pardiso solver instance
     let mut ps = PanuaPardisoSolver::new().unwrap();
-    test_symmetric(&mut ps);
+    test_symmetric(&mut ps, IndexBase::One);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_symmetric_panua_zero_based() {
+    use crate::*;
+    // Panua is passed a one-based copy of the structure
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_symmetric(&mut ps, IndexBase::Zero);
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_inertia(ps: &mut dyn crate::PardisoInterface) {
+    use super::test_matrix;
+    use crate::*;
+    let matrix = test_matrix();
+
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+
+    let inertia = ps.get_inertia().unwrap();
+    assert_eq!(
+        (inertia.positive, inertia.negative, inertia.zero),
+        (3, 1, 0)
+    );
+    assert!(!inertia.is_singular());
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_inertia_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_inertia(&mut ps);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_inertia_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_inertia(&mut ps);
+}
+
+#[cfg(any(feature = "mkl", feature = "panua"))]
+fn test_stats(ps: &mut dyn crate::PardisoInterface) {
+    use super::test_matrix;
+    use crate::*;
+    let matrix = test_matrix();
+
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+
+    // nothing is reported before the first call
+    let stats = ps.get_stats();
+    assert_eq!(stats.factor_memory_kb, None);
+    assert_eq!(stats.factor_nnz, None);
+
+    ps.set_phase(Phase::AnalysisNumFact);
+    ps.pardiso(&matrix, &mut [], &mut [], 1).unwrap();
+
+    let stats = ps.get_stats();
+    assert_eq!(stats.phase, Phase::AnalysisNumFact);
+    assert!(stats.factor_nnz.unwrap() >= 9);
+    assert!(stats.peak_memory_kb().is_some());
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_stats_mkl() {
+    use crate::*;
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    test_stats(&mut ps);
+}
+
+#[cfg(feature = "panua")]
+#[test]
+fn test_stats_panua() {
+    use crate::*;
+    let mut ps = PanuaPardisoSolver::new().unwrap();
+    test_stats(&mut ps);
+}
+
+#[cfg(feature = "mkl")]
+#[test]
+fn test_symmetric_mkl_ilp64() {
+    use super::{test_matrix, TEST_A, TEST_IA, TEST_JA};
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    // same problem as test_symmetric, with 64-bit indices
+    let n: usize = 4;
+    let m: i32 = 3;
+    let ia: Vec<i64> = TEST_IA.iter().map(|&i| i.into()).collect();
+    let ja: Vec<i64> = TEST_JA.iter().map(|&j| j.into()).collect();
+    let matrix = CsrMatrix::new(n, &TEST_A, &ia, &ja).unwrap();
+
+    let mut b: Vec<f64> = (0..(n * m as usize)).map(|x| x as f64).collect();
+    let mut x = vec![0.0; n * m as usize];
+
+    let mut ps = MKLPardisoSolver::new().unwrap();
+    ps.set_matrix_type(MatrixType::RealSymmetricIndefinite);
+    ps.pardisoinit().unwrap();
+
+    ps.set_phase(Phase::Analysis);
+    ps.pardiso_generic(&matrix, &mut [], &mut [], 1).unwrap();
+
+    // 32-bit calls are rejected on a 64-bit handle
+    ps.set_phase(Phase::NumFact);
+    assert!(matches!(
+        ps.pardiso(&test_matrix(), &mut [], &mut [], 1),
+        Err(PardisoError::IndexWidthMismatch)
+    ));
+
+    ps.pardiso_generic(&matrix, &mut [], &mut [], 1).unwrap();
+    assert!(ps.get_num_positive_eigenvalues() == 3);
+
+    // statistics are read from the full width iparm
+    assert!(ps.get_iparm64(17) >= 9);
+    assert_eq!(ps.get_stats().factor_nnz, Some(ps.get_iparm64(17)));
+    assert!(ps.get_num_negative_eigenvalues() == 1);
+
+    ps.set_phase(Phase::SolveIterativeRefine);
+    ps.pardiso_generic(&matrix, &mut b, &mut x, m).unwrap();
+
+    let xans = vec![
+        16.0, -0.0, 5.0, -2.0, 207.2, -5.6, 72.2, -19.6, 398.4, -11.2, 139.4, -37.2,
+    ];
+    assert_abs_diff_eq!(x.as_slice(), xans.as_slice(), epsilon = 1e-6);
 }